@@ -1,6 +1,13 @@
-use std::ffi::{c_long, c_ushort};
+use std::{
+    ffi::{c_long, c_ushort, c_void},
+    ptr,
+};
 
-use super::{provided_types::TVariant, string_utils::os_string_nil};
+use super::{
+    memory_manager::MemoryManager,
+    provided_types::{DataBlob, DataStr, TVariant, VariantType},
+};
+use crate::interface::ParamValue;
 
 /// Message codes that can be used in `Connection::add_error` method
 /// to specify message type.
@@ -28,6 +35,32 @@ pub enum MessageCode {
     DialogFail = 1009,
 }
 
+/// UTF-16 string with null terminator, the platform reads strings up to it
+fn wide_str(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(Some(0)).collect()
+}
+
+/// Frees string or binary data of the value and error description, that the
+/// platform has allocated in `Read`. Without memory manager of the platform
+/// they can't be freed and are left as is
+/// # Safety
+/// Pointers must be allocated by the platform or be null
+unsafe fn free_read_buffers(variant: &TVariant, error_description: *mut u16) {
+    let Some(mem) = MemoryManager::platform() else {
+        return;
+    };
+    let data = match variant.vt {
+        VariantType::WStr => variant.value.data_str.ptr.cast::<c_void>(),
+        VariantType::Blob => variant.value.data_blob.ptr.cast::<c_void>(),
+        _ => ptr::null_mut(),
+    };
+    for mut ptr in [data, error_description.cast::<c_void>()] {
+        if !ptr.is_null() {
+            mem.free_memory(&mut ptr);
+        }
+    }
+}
+
 /// VTable for Connection object, derived from Native API interface. See original
 /// C++ implementation in [example project](https://its.1c.ru/db/files/1CITS/EXE/VNCOMPS/VNCOMPS.zip)
 /// from 1C documentation
@@ -47,7 +80,7 @@ struct ConnectionVTable {
         &Connection,
         *mut u16,
         &mut TVariant,
-        c_long,
+        *mut c_long,
         *mut *mut u16,
     ) -> bool,
     write:
@@ -89,8 +122,8 @@ impl Connection {
         description: &str,
     ) -> bool {
         unsafe {
-            let source_wstr = os_string_nil(source);
-            let description_wstr = os_string_nil(description);
+            let source_wstr = wide_str(source);
+            let description_wstr = wide_str(description);
             (self.vptr1.add_error)(
                 self,
                 code as u16,
@@ -110,9 +143,9 @@ impl Connection {
     /// `bool` - operation success status
     pub fn external_event(&self, caller: &str, name: &str, data: &str) -> bool {
         unsafe {
-            let mut caller_wstr = os_string_nil(caller);
-            let mut name_wstr = os_string_nil(name);
-            let mut data_wstr = os_string_nil(data);
+            let mut caller_wstr = wide_str(caller);
+            let mut name_wstr = wide_str(name);
+            let mut data_wstr = wide_str(data);
             (self.vptr1.external_event)(
                 self,
                 caller_wstr.as_mut_ptr(),
@@ -135,4 +168,110 @@ impl Connection {
     pub fn get_event_buffer_depth(&self) -> c_long {
         unsafe { (self.vptr1.get_event_buffer_depth)(self) }
     }
+
+    /// Equivalent to `SetStatusLine` from Native API interface and is used to
+    /// show a text in the status line of 1C, e.g. progress of a long operation
+    /// # Arguments
+    /// * `status_line` - text to show
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn set_status_line(&self, status_line: &str) -> bool {
+        unsafe {
+            let mut status_line_wstr = wide_str(status_line);
+            (self.vptr1.set_status_line)(self, status_line_wstr.as_mut_ptr())
+        }
+    }
+
+    /// Equivalent to `ResetStatusLine` from Native API interface and is used
+    /// to restore the status line, changed with `set_status_line`
+    pub fn reset_status_line(&self) {
+        unsafe { (self.vptr1.reset_status_line)(self) }
+    }
+
+    /// Equivalent to `RegisterProfileAs` from Native API interface and is used
+    /// to set the name of the profile, where `read` and `write` store values
+    /// of the AddIn for the current user
+    /// # Arguments
+    /// * `profile_name` - name of the profile
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn register_profile_as(&self, profile_name: &str) -> bool {
+        unsafe {
+            let mut profile_name_wstr = wide_str(profile_name);
+            (self.vptr1.register_profile_as)(
+                self,
+                profile_name_wstr.as_mut_ptr(),
+            )
+        }
+    }
+
+    /// Equivalent to `Read` from Native API interface and is used to read
+    /// a value from the profile, see `register_profile_as`. The value is
+    /// copied, and memory, allocated for it by the platform, is freed
+    /// # Arguments
+    /// * `name` - name of the value
+    /// # Returns
+    /// `Option<ParamValue>` - stored value, `None` if it is not stored
+    pub fn read(&self, name: &str) -> Option<ParamValue> {
+        let mut name_wstr = wide_str(name);
+        let mut variant = TVariant::default();
+        let mut error_code: c_long = 0;
+        let mut error_description: *mut u16 = ptr::null_mut();
+        let success = unsafe {
+            (self.vptr1.read)(
+                self,
+                name_wstr.as_mut_ptr(),
+                &mut variant,
+                &mut error_code,
+                &mut error_description,
+            )
+        };
+        let value = match success {
+            true => Some(ParamValue::from(&variant)),
+            false => None,
+        };
+        unsafe { free_read_buffers(&variant, error_description) };
+        match value {
+            Some(ParamValue::Empty) | None => None,
+            value => value,
+        }
+    }
+
+    /// Equivalent to `Write` from Native API interface and is used to store
+    /// a value in the profile, see `register_profile_as`
+    /// # Arguments
+    /// * `name` - name of the value
+    /// * `value` - value to store
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn write(&self, name: &str, value: &ParamValue) -> bool {
+        let mut name_wstr = wide_str(name);
+        let mut variant = TVariant::default();
+        match value {
+            ParamValue::Empty => {}
+            ParamValue::Bool(v) => variant.update_to_bool(*v),
+            ParamValue::I32(v) => variant.update_to_i32(*v),
+            ParamValue::I64(v) => variant.update_to_i64(*v),
+            ParamValue::F64(v) => variant.update_to_f64(*v),
+            ParamValue::Date(v) => variant.update_to_date(*v),
+            // platform copies the value, so it can point to our buffers
+            ParamValue::String(v) => {
+                variant.value.data_str = DataStr {
+                    ptr: v.as_ptr().cast_mut(),
+                    len: v.len() as u32,
+                };
+                variant.vt = VariantType::WStr;
+            }
+            ParamValue::Blob(v) => {
+                variant.value.data_blob = DataBlob {
+                    ptr: v.as_ptr().cast_mut(),
+                    len: v.len() as u32,
+                };
+                variant.vt = VariantType::Blob;
+            }
+        }
+        unsafe {
+            (self.vptr1.write)(self, name_wstr.as_mut_ptr(), &mut variant)
+        }
+    }
 }
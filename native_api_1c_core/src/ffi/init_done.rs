@@ -31,6 +31,7 @@ unsafe extern "system" fn set_mem_manager<T: AddInWrapper>(
 ) -> bool {
     let component = this.get_component();
     component.memory_manager_ptr = Some(mem);
+    MemoryManager::set_platform(mem);
     true
 }
 
@@ -249,6 +249,17 @@ unsafe extern "system" fn has_ret_val<T: AddInWrapper>(
     component.addin.has_ret_val(method_num as usize)
 }
 
+/// Parameters passed by 1C, host can pass null pointer when there are none
+unsafe fn params_slice<'a>(
+    params: *mut TVariant,
+    size_array: c_long,
+) -> &'a mut [TVariant] {
+    if params.is_null() || size_array <= 0 {
+        return &mut [];
+    }
+    from_raw_parts_mut(params, size_array as usize)
+}
+
 unsafe extern "system" fn call_as_proc<T: AddInWrapper>(
     this: &mut This<T>,
     method_num: c_long,
@@ -260,7 +271,7 @@ unsafe extern "system" fn call_as_proc<T: AddInWrapper>(
         return false;
     };
 
-    let parameters_raw = from_raw_parts_mut(params, size_array as usize);
+    let parameters_raw = params_slice(params, size_array);
     let mut parameters_values =
         ParamValues::new(parameters_raw.iter().map(ParamValue::from).collect());
 
@@ -272,8 +283,10 @@ unsafe extern "system" fn call_as_proc<T: AddInWrapper>(
         return false;
     }
 
-    for (i, param) in parameters_values.iter().enumerate() {
-        parameters_raw[i].update_from_return(mem_mngr, param);
+    // values, added for missing parameters, are not written back
+    for (raw, param) in parameters_raw.iter_mut().zip(parameters_values.iter())
+    {
+        raw.update_from_return(mem_mngr, param);
     }
 
     true
@@ -291,7 +304,7 @@ unsafe extern "system" fn call_as_func<T: AddInWrapper>(
         return false;
     };
 
-    let parameters_raw = from_raw_parts_mut(params, size_array as usize);
+    let parameters_raw = params_slice(params, size_array);
     let mut parameters_values =
         ParamValues::new(parameters_raw.iter().map(ParamValue::from).collect());
 
@@ -305,8 +318,10 @@ unsafe extern "system" fn call_as_func<T: AddInWrapper>(
 
     ret_value.update_from_return(mem_mngr, &ret_val);
 
-    for (i, param) in parameters_values.iter().enumerate() {
-        parameters_raw[i].update_from_return(mem_mngr, param);
+    // values, added for missing parameters, are not written back
+    for (raw, param) in parameters_raw.iter_mut().zip(parameters_values.iter())
+    {
+        raw.update_from_return(mem_mngr, param);
     }
 
     true
@@ -1,8 +1,16 @@
 use std::{
     ffi::{c_ulong, c_void},
     ptr::{self, NonNull},
+    sync::OnceLock,
 };
 
+/// Memory manager, passed by the platform to the first initialized object.
+/// The platform has one allocator, so it is used to free memory, that the
+/// platform allocates outside of object calls, e.g. values of
+/// `Connection::read`
+static PLATFORM_MEMORY_MANAGER: OnceLock<&'static MemoryManager> =
+    OnceLock::new();
+
 /// VTable for MemoryManager object, derived from Native API interface. See original
 /// C++ implementation in [example project](https://its.1c.ru/db/files/1CITS/EXE/VNCOMPS/VNCOMPS.zip)
 /// from 1C documentation
@@ -28,6 +36,16 @@ pub struct MemoryManager {
 pub struct AllocationError;
 
 impl MemoryManager {
+    /// Returns memory manager of the platform, if any object has received it
+    pub(crate) fn platform() -> Option<&'static MemoryManager> {
+        PLATFORM_MEMORY_MANAGER.get().copied()
+    }
+
+    /// Stores memory manager of the platform, see `platform`
+    pub(crate) fn set_platform(mem: &'static MemoryManager) {
+        let _ = PLATFORM_MEMORY_MANAGER.set(mem);
+    }
+
     /// Safe wrapper around `alloc_memory` method of the MemoryManager object
     /// to allocate memory for byte array
     /// # Arguments
@@ -3,8 +3,9 @@
 //! submodules according to what C++ class they originate from
 //!
 use std::{
-    ffi::{c_long, c_void},
+    ffi::{c_int, c_long, c_void},
     ptr,
+    sync::atomic::{AtomicI32, Ordering},
 };
 
 use crate::interface::AddInWrapper;
@@ -46,6 +47,61 @@ pub enum AttachType {
     Any,
 }
 
+/// Capabilities level of 1C platform, passed to the AddIn with
+/// `SetPlatformCapabilities` call. Platform versions, that do not call it,
+/// are represented by `Invalid`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AppCapabilities {
+    /// Capabilities were not passed by the platform
+    Invalid = -1,
+    /// First capabilities level
+    Capabilities1 = 1,
+    /// Second capabilities level
+    Capabilities2 = 2,
+    /// Third capabilities level, latest supported by this crate
+    Capabilities3 = 3,
+}
+
+impl AppCapabilities {
+    /// Latest capabilities level supported by this crate
+    pub const LAST: Self = Self::Capabilities3;
+}
+
+impl From<c_int> for AppCapabilities {
+    fn from(value: c_int) -> Self {
+        match value {
+            1 => Self::Capabilities1,
+            2 => Self::Capabilities2,
+            // newer platforms still support everything known to us
+            v if v >= 3 => Self::Capabilities3,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+static PLATFORM_CAPABILITIES: AtomicI32 =
+    AtomicI32::new(AppCapabilities::Invalid as i32);
+
+/// Returns capabilities level of 1C platform, that loaded the library
+/// # Returns
+/// `AppCapabilities` - capabilities level, `AppCapabilities::Invalid` if
+/// platform has not passed it
+pub fn platform_capabilities() -> AppCapabilities {
+    PLATFORM_CAPABILITIES.load(Ordering::Relaxed).into()
+}
+
+/// Stores capabilities level of 1C platform, intended to be called from
+/// `SetPlatformCapabilities` export, generated by `extern_functions!`
+/// # Arguments
+/// * `capabilities` - capabilities level, passed by the platform
+/// # Returns
+/// `c_int` - capabilities level supported by the AddIn
+pub fn set_platform_capabilities(capabilities: c_int) -> c_int {
+    PLATFORM_CAPABILITIES.store(capabilities, Ordering::Relaxed);
+    AppCapabilities::LAST as c_int
+}
+
 /// Struct to extract pointer to `Component` from it's interface components
 /// In some places we need to get pointer to `Component` from it's interface
 /// components, so we need to calculate offset of `Component` in memory
@@ -61,8 +117,8 @@ mod offset {
     pub const USER_LANG: usize = 3;
 }
 
-impl<'a, const OFFSET: usize, T: AddInWrapper> This<OFFSET, T> {
-    unsafe fn get_component(&mut self) -> &'a mut Component<T> {
+impl<const OFFSET: usize, T: AddInWrapper> This<OFFSET, T> {
+    unsafe fn get_component(&mut self) -> &mut Component<T> {
         let new_ptr = (self as *mut This<OFFSET, T> as *mut c_void)
             .sub(OFFSET * std::mem::size_of::<usize>());
         &mut *(new_ptr as *mut Component<T>)
@@ -77,6 +133,9 @@ struct Component<T: AddInWrapper> {
     locale_ptr: Box<LocaleBaseVTable<T>>,
     usr_lang_ptr: Box<UserLanguageBaseVTable<T>>,
 
+    // must follow the interfaces, `destroy_component` reads it at this offset
+    destroy: unsafe extern "system" fn(*mut *mut Component<T>),
+
     // storage for additional interfaces
     memory_manager_ptr: Option<&'static MemoryManager>,
     connection_ptr: Option<&'static Connection>,
@@ -84,7 +143,6 @@ struct Component<T: AddInWrapper> {
     user_interface_language_code: Option<String>,
 
     // rust part
-    destroy: unsafe extern "system" fn(*mut *mut Component<T>),
     addin: T,
 }
 
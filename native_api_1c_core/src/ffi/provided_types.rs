@@ -173,6 +173,12 @@ impl<'a> ReturnValue<'a> {
         self.variant.value.i32 = val;
     }
 
+    /// Sets the value of the ReturnValue object to integer `i64`
+    pub fn set_i64(self, val: i64) {
+        self.variant.vt = VariantType::Int64;
+        self.variant.value.i64 = val;
+    }
+
     /// Sets the value of the ReturnValue object to bool `bool`
     pub fn set_bool(self, val: bool) {
         self.variant.vt = VariantType::Bool;
@@ -231,6 +237,13 @@ impl<'a> From<&'a TVariant> for ParamValue {
                 VariantType::Empty => Self::Empty,
                 VariantType::Bool => Self::Bool(param.value.bool),
                 VariantType::Int32 => Self::I32(param.value.i32),
+                VariantType::Int8 => Self::I32(param.value.i8.into()),
+                VariantType::Int16 => Self::I32(param.value.i16.into()),
+                VariantType::UInt8 => Self::I32(param.value.u8.into()),
+                VariantType::UInt16 => Self::I32(param.value.u16.into()),
+                VariantType::UInt32 => Self::from_i64(param.value.u32.into()),
+                VariantType::Int64 => Self::from_i64(param.value.i64),
+                VariantType::UInt64 => Self::from_u64(param.value.u64),
                 VariantType::Double => Self::F64(param.value.f64),
                 VariantType::Time => Self::Date(param.value.tm),
                 VariantType::WStr => Self::String(
@@ -312,6 +325,8 @@ pub struct DataBlob {
 /// # Fields
 /// * `bool` - boolean value
 /// * `i32` - integer value
+/// * `i8`, `i16`, `i64`, `u8`, `u16`, `u32`, `u64` - integer values
+///   of other widths
 /// * `f64` - float value
 /// * `tm` - date-time value
 /// * `data_str` - UTF-16 string value
@@ -320,6 +335,13 @@ pub struct DataBlob {
 pub union VariantValue {
     pub bool: bool,
     pub i32: i32,
+    pub i8: i8,
+    pub i16: i16,
+    pub i64: i64,
+    pub u8: u8,
+    pub u16: u16,
+    pub u32: u32,
+    pub u64: u64,
     pub f64: f64,
     pub tm: Tm,
     pub data_str: DataStr,
@@ -401,6 +423,11 @@ impl TVariant {
         self.vt = VariantType::Int32;
     }
 
+    pub fn update_to_i64(&mut self, v: i64) {
+        self.value.i64 = v;
+        self.vt = VariantType::Int64;
+    }
+
     pub fn update_to_f64(&mut self, v: f64) {
         self.value.f64 = v;
         self.vt = VariantType::Double;
@@ -420,6 +447,7 @@ impl TVariant {
             ParamValue::Empty => self.vt = VariantType::Empty,
             ParamValue::Bool(v) => self.update_to_bool(*v),
             ParamValue::I32(v) => self.update_to_i32(*v),
+            ParamValue::I64(v) => self.update_to_i64(*v),
             ParamValue::F64(v) => self.update_to_f64(*v),
             ParamValue::Date(v) => self.update_to_date(*v),
             ParamValue::String(v) => {
@@ -0,0 +1,130 @@
+use super::ParamValue;
+use crate::ffi::{provided_types::Tm, string_utils::from_os_string};
+
+/// Rules of converting values received from 1C to the type, expected
+/// by the AddIn, when they don't match exactly
+/// # Fields
+/// * `numbers` - integers are accepted as float, and `F64` without
+///   fractional part, that fits into the integer type, is accepted as integer
+/// * `bool_int` - `Bool` is accepted as integer (`1` or `0`) and integer
+///   is accepted as `Bool` (`true` if not `0`)
+/// * `to_string` - numbers and dates are accepted as strings, dates are
+///   formatted as in `XMLСтрока`, e.g. `2024-01-31T12:00:00`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Coercion {
+    pub numbers: bool,
+    pub bool_int: bool,
+    pub to_string: bool,
+}
+
+impl Coercion {
+    /// No conversions, value must have exactly the expected type
+    pub const STRICT: Self = Self {
+        numbers: false,
+        bool_int: false,
+        to_string: false,
+    };
+
+    /// Conversions between numbers only, used by default
+    pub const NUMBERS: Self = Self {
+        numbers: true,
+        bool_int: false,
+        to_string: false,
+    };
+}
+
+impl Default for Coercion {
+    fn default() -> Self {
+        Self::NUMBERS
+    }
+}
+
+impl ParamValue {
+    /// Returns `Some` if value is `I32` or can be converted to it
+    /// with given `coercion` rules
+    pub fn coerce_to_i32(&self, coercion: Coercion) -> Option<i32> {
+        match self {
+            Self::I32(val) => Some(*val),
+            Self::I64(val) if coercion.numbers => i32::try_from(*val).ok(),
+            Self::F64(val)
+                if coercion.numbers
+                    && val.fract() == 0.0
+                    && *val >= i32::MIN as f64
+                    && *val <= i32::MAX as f64 =>
+            {
+                Some(*val as i32)
+            }
+            Self::Bool(val) if coercion.bool_int => Some((*val).into()),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `I32` or `I64`, or can be converted to
+    /// `i64` with given `coercion` rules. 1C passes integers outside of
+    /// `i32` range as `F64`, so they are accepted only with `numbers`
+    pub fn coerce_to_i64(&self, coercion: Coercion) -> Option<i64> {
+        match self {
+            Self::I32(_) | Self::I64(_) => self.as_i64(),
+            Self::F64(_) if coercion.numbers => self.as_i64(),
+            Self::Bool(val) if coercion.bool_int => Some((*val).into()),
+            _ => None,
+        }
+    }
+
+    /// Same as `coerce_to_i64`, but for values in `u64` range
+    pub fn coerce_to_u64(&self, coercion: Coercion) -> Option<u64> {
+        match self {
+            Self::I32(_) | Self::I64(_) => self.as_u64(),
+            Self::F64(_) if coercion.numbers => self.as_u64(),
+            Self::Bool(val) if coercion.bool_int => Some((*val).into()),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `F64` or can be converted to it
+    /// with given `coercion` rules
+    pub fn coerce_to_f64(&self, coercion: Coercion) -> Option<f64> {
+        match self {
+            Self::F64(val) => Some(*val),
+            Self::I32(val) if coercion.numbers => Some((*val).into()),
+            Self::I64(val) if coercion.numbers => Some(*val as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `Bool` or can be converted to it
+    /// with given `coercion` rules
+    pub fn coerce_to_bool(&self, coercion: Coercion) -> Option<bool> {
+        match self {
+            Self::Bool(val) => Some(*val),
+            Self::I32(val) if coercion.bool_int => Some(*val != 0),
+            Self::I64(val) if coercion.bool_int => Some(*val != 0),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `String` or can be converted to it
+    /// with given `coercion` rules
+    pub fn coerce_to_string(&self, coercion: Coercion) -> Option<String> {
+        match self {
+            Self::String(val) => Some(from_os_string(val)),
+            Self::I32(val) if coercion.to_string => Some(val.to_string()),
+            Self::I64(val) if coercion.to_string => Some(val.to_string()),
+            Self::F64(val) if coercion.to_string => Some(val.to_string()),
+            Self::Date(val) if coercion.to_string => Some(format_date(val)),
+            _ => None,
+        }
+    }
+}
+
+fn format_date(tm: &Tm) -> String {
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        tm.year + 1900,
+        tm.mon + 1,
+        tm.mday,
+        tm.hour,
+        tm.min,
+        tm.sec
+    )
+}
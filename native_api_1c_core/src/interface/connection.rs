@@ -0,0 +1,92 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::ffi::connection::Connection;
+
+/// Field of the AddIn, that receives connection to 1C platform when the AddIn
+/// is initialized. Implemented for the types that can be marked with
+/// `#[add_in_con]`
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as `add_in_con` field",
+    note = "use `Option<&'static Connection>`, `Arc<Option<&'static Connection>>`, `Mutex<Option<&'static Connection>>` or `ConnectionHandle`"
+)]
+pub trait ConnectionField {
+    /// Stores the connection in the field
+    /// # Arguments
+    /// * `connection` - connection to 1C platform, valid until the AddIn is
+    ///   unloaded
+    fn set_connection(&mut self, connection: &'static Connection);
+
+    /// Returns the connection, if it is already set
+    fn connection(&self) -> Option<&'static Connection>;
+}
+
+impl ConnectionField for Option<&'static Connection> {
+    fn set_connection(&mut self, connection: &'static Connection) {
+        *self = Some(connection);
+    }
+
+    fn connection(&self) -> Option<&'static Connection> {
+        *self
+    }
+}
+
+impl ConnectionField for Arc<Option<&'static Connection>> {
+    fn set_connection(&mut self, connection: &'static Connection) {
+        *self = Arc::new(Some(connection));
+    }
+
+    fn connection(&self) -> Option<&'static Connection> {
+        **self
+    }
+}
+
+impl ConnectionField for Mutex<Option<&'static Connection>> {
+    fn set_connection(&mut self, connection: &'static Connection) {
+        let value = self.get_mut().unwrap_or_else(|err| err.into_inner());
+        *value = Some(connection);
+    }
+
+    fn connection(&self) -> Option<&'static Connection> {
+        *self.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl ConnectionField for Arc<Mutex<Option<&'static Connection>>> {
+    fn set_connection(&mut self, connection: &'static Connection) {
+        // value is set in place, so clones made before `init` see it too
+        let mut value = self.lock().unwrap_or_else(|err| err.into_inner());
+        *value = Some(connection);
+    }
+
+    fn connection(&self) -> Option<&'static Connection> {
+        *self.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl ConnectionField for ConnectionHandle {
+    fn set_connection(&mut self, connection: &'static Connection) {
+        let _ = self.0.set(connection);
+    }
+
+    fn connection(&self) -> Option<&'static Connection> {
+        self.get()
+    }
+}
+
+/// Cloneable handle to the connection, that is set once the AddIn is
+/// initialized. Clones share the same connection, so the handle can be
+/// passed to background threads before `init` is called
+#[derive(Clone, Default)]
+pub struct ConnectionHandle(Arc<OnceLock<&'static Connection>>);
+
+impl ConnectionHandle {
+    /// Creates a handle without connection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the connection, if the AddIn is already initialized
+    pub fn get(&self) -> Option<&'static Connection> {
+        self.0.get().copied()
+    }
+}
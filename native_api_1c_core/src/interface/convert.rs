@@ -0,0 +1,335 @@
+use std::fmt::Display;
+
+use super::{Coercion, ParamValue};
+use crate::ffi::{provided_types::Tm, string_utils::os_string};
+
+/// Error of converting value received from 1C to Rust type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConversionError {
+    message: String,
+}
+
+impl ConversionError {
+    /// Creates error with given message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// Creates error for value of unexpected type, e.g.
+    /// "expected Number, got String"
+    /// # Arguments
+    /// * `expected` - name of expected 1C type
+    /// * `value` - received value
+    pub fn unexpected_type(expected: &str, value: &ParamValue) -> Self {
+        Self::new(format!("expected {expected}, got {}", value.type_name()))
+    }
+
+    /// Returns error message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Conversion of value received from 1C to Rust type. Implemented for
+/// all built-in types, and can be implemented for user types to use them
+/// with `ty = Custom` in `#[derive(AddIn)]`
+pub trait FromParamValue: Sized {
+    /// Converts `value` to `Self`
+    /// # Arguments
+    /// * `value` - value received from 1C
+    /// * `coercion` - conversions, set for the argument or property
+    /// # Returns
+    /// `Result<Self, ConversionError>` - converted value, or error if
+    /// `value` has unexpected type
+    fn from_param_value(
+        value: &ParamValue,
+        coercion: Coercion,
+    ) -> Result<Self, ConversionError>;
+}
+
+/// Conversion of Rust type to value passed to 1C. Implemented for
+/// all built-in types, and can be implemented for user types to use them
+/// with `ty = Custom` in `#[derive(AddIn)]`
+pub trait IntoParamValue {
+    /// Converts `self` to value passed to 1C
+    fn into_param_value(self) -> ParamValue;
+
+    /// Same as `into_param_value`, but for conversions that can fail, e.g.
+    /// serialization. Used by `#[derive(AddIn)]`, so that the error is
+    /// reported to 1C
+    fn try_into_param_value(self) -> Result<ParamValue, ConversionError>
+    where
+        Self: Sized,
+    {
+        Ok(self.into_param_value())
+    }
+}
+
+impl FromParamValue for ParamValue {
+    fn from_param_value(
+        value: &ParamValue,
+        _coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        Ok(value.clone())
+    }
+}
+
+impl IntoParamValue for ParamValue {
+    fn into_param_value(self) -> ParamValue {
+        self
+    }
+}
+
+impl IntoParamValue for () {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::Empty
+    }
+}
+
+impl FromParamValue for bool {
+    fn from_param_value(
+        value: &ParamValue,
+        coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        value
+            .coerce_to_bool(coercion)
+            .ok_or_else(|| ConversionError::unexpected_type("Boolean", value))
+    }
+}
+
+impl IntoParamValue for bool {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::Bool(self)
+    }
+}
+
+impl FromParamValue for i32 {
+    fn from_param_value(
+        value: &ParamValue,
+        coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        value
+            .coerce_to_i32(coercion)
+            .ok_or_else(|| ConversionError::unexpected_type("Number", value))
+    }
+}
+
+impl IntoParamValue for i32 {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::I32(self)
+    }
+}
+
+impl FromParamValue for f64 {
+    fn from_param_value(
+        value: &ParamValue,
+        coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        value
+            .coerce_to_f64(coercion)
+            .ok_or_else(|| ConversionError::unexpected_type("Number", value))
+    }
+}
+
+impl IntoParamValue for f64 {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::F64(self)
+    }
+}
+
+impl FromParamValue for String {
+    fn from_param_value(
+        value: &ParamValue,
+        coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        value
+            .coerce_to_string(coercion)
+            .ok_or_else(|| ConversionError::unexpected_type("String", value))
+    }
+}
+
+impl IntoParamValue for String {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::String(os_string(&self))
+    }
+}
+
+impl IntoParamValue for &str {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::String(os_string(self))
+    }
+}
+
+impl FromParamValue for Tm {
+    fn from_param_value(
+        value: &ParamValue,
+        _coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        value
+            .as_date()
+            .ok_or_else(|| ConversionError::unexpected_type("Date", value))
+    }
+}
+
+impl IntoParamValue for Tm {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::Date(self)
+    }
+}
+
+impl FromParamValue for chrono::NaiveDateTime {
+    fn from_param_value(
+        value: &ParamValue,
+        _coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        value
+            .as_date()
+            .map(Into::into)
+            .ok_or_else(|| ConversionError::unexpected_type("Date", value))
+    }
+}
+
+impl IntoParamValue for chrono::NaiveDateTime {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::Date(self.into())
+    }
+}
+
+impl FromParamValue for Vec<u8> {
+    fn from_param_value(
+        value: &ParamValue,
+        _coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        value.as_blob().map(<[u8]>::to_vec).ok_or_else(|| {
+            ConversionError::unexpected_type("BinaryData", value)
+        })
+    }
+}
+
+impl IntoParamValue for Vec<u8> {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::Blob(self)
+    }
+}
+
+macro_rules! impl_integer {
+    ($ty:ty, $coerce_fn:ident, $from_fn:ident, $wide_ty:ty) => {
+        impl FromParamValue for $ty {
+            fn from_param_value(
+                value: &ParamValue,
+                coercion: Coercion,
+            ) -> Result<Self, ConversionError> {
+                let val = value.$coerce_fn(coercion).ok_or_else(|| {
+                    ConversionError::unexpected_type("integer Number", value)
+                })?;
+                <$ty>::try_from(val).map_err(|_| {
+                    ConversionError::new(format!(
+                        "value {val} is out of range for {}",
+                        stringify!($ty)
+                    ))
+                })
+            }
+        }
+
+        impl IntoParamValue for $ty {
+            fn into_param_value(self) -> ParamValue {
+                ParamValue::$from_fn(self as $wide_ty)
+            }
+        }
+    };
+}
+
+impl_integer!(i16, coerce_to_i64, from_i64, i64);
+impl_integer!(i64, coerce_to_i64, from_i64, i64);
+impl_integer!(u8, coerce_to_i64, from_i64, i64);
+impl_integer!(u32, coerce_to_i64, from_i64, i64);
+impl_integer!(u64, coerce_to_u64, from_u64, u64);
+impl_integer!(usize, coerce_to_u64, from_u64, u64);
+
+/// Numbers that don't fit into `Decimal` are reported as such, and not as
+/// values of unexpected type
+#[cfg(feature = "decimal")]
+impl FromParamValue for rust_decimal::Decimal {
+    fn from_param_value(
+        value: &ParamValue,
+        _coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        if let Some(val) = value.as_decimal() {
+            return Ok(val);
+        }
+        let number = match value {
+            ParamValue::F64(val) => val.to_string(),
+            ParamValue::String(val) => {
+                let val = crate::ffi::string_utils::from_os_string(val);
+                if !is_decimal_number(val.trim()) {
+                    return Err(ConversionError::unexpected_type(
+                        "Number", value,
+                    ));
+                }
+                val.trim().to_string()
+            }
+            _ => return Err(ConversionError::unexpected_type("Number", value)),
+        };
+        Err(ConversionError::new(format!(
+            "number {number} doesn't fit into Decimal, \
+            which holds at most 28 significant digits"
+        )))
+    }
+}
+
+/// Checks that `val` is a number written with `.` as decimal separator,
+/// e.g. `-1234.50`
+#[cfg(feature = "decimal")]
+fn is_decimal_number(val: &str) -> bool {
+    let val = val.strip_prefix(['-', '+']).unwrap_or(val);
+    let (int, frac) = val.split_once('.').unwrap_or((val, ""));
+    !(int.is_empty() && frac.is_empty())
+        && int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+}
+
+#[cfg(feature = "decimal")]
+impl IntoParamValue for rust_decimal::Decimal {
+    fn into_param_value(self) -> ParamValue {
+        ParamValue::from_decimal(self)
+    }
+}
+
+/// `Empty` (`Undefined` in 1C) is `None`
+impl<T: FromParamValue> FromParamValue for Option<T> {
+    fn from_param_value(
+        value: &ParamValue,
+        coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        match value {
+            ParamValue::Empty => Ok(None),
+            value => T::from_param_value(value, coercion).map(Some),
+        }
+    }
+}
+
+/// `None` is `Empty` (`Undefined` in 1C)
+impl<T: IntoParamValue> IntoParamValue for Option<T> {
+    fn into_param_value(self) -> ParamValue {
+        match self {
+            Some(value) => value.into_param_value(),
+            None => ParamValue::Empty,
+        }
+    }
+
+    fn try_into_param_value(self) -> Result<ParamValue, ConversionError> {
+        match self {
+            Some(value) => value.try_into_param_value(),
+            None => Ok(ParamValue::Empty),
+        }
+    }
+}
@@ -0,0 +1,851 @@
+use std::fmt::Display;
+
+use super::{
+    AddInWrapper, AddInWrapperResult, Coercion, ConversionError,
+    FromParamValue, ParamValue, ParamValues,
+};
+use crate::ffi::{
+    connection::{Connection, MessageCode},
+    string_utils::{from_os_string, os_string, os_string_nil},
+};
+
+/// Description of the AddIn property, used in `AddInDescriptor` tables
+/// # Fields
+/// * `name` - property name in 1C
+/// * `name_ru` - property name in 1C in Russian
+/// * `getter` - function reading the property, `None` if it is not readable
+/// * `setter` - function writing the property, `None` if it is not writable
+pub struct PropDescriptor<T> {
+    pub name: &'static str,
+    pub name_ru: &'static str,
+    pub getter: Option<fn(&T) -> AddInWrapperResult<ParamValue>>,
+    pub setter: Option<fn(&mut T, ParamValue) -> AddInWrapperResult<()>>,
+}
+
+/// Description of the AddIn method parameter
+/// # Fields
+/// * `default` - function producing the default value of the parameter
+pub struct ParamDescriptor {
+    pub default: Option<fn() -> ParamValue>,
+}
+
+/// Description of the AddIn method, used in `AddInDescriptor` tables
+/// # Fields
+/// * `name` - method name in 1C
+/// * `name_ru` - method name in 1C in Russian
+/// * `params` - descriptions of parameters, visible to 1C
+/// * `has_ret_val` - if method returns a value
+/// * `call` - function calling the method, returns `ParamValue::Empty`
+///   for methods without return value
+pub struct MethodDescriptor<T> {
+    pub name: &'static str,
+    pub name_ru: &'static str,
+    pub params: &'static [ParamDescriptor],
+    pub has_ret_val: bool,
+    pub call: fn(&mut T, &mut ParamValues) -> Result<ParamValue, MethodError>,
+}
+
+/// Description of the nested AddIn, whose properties and methods are merged
+/// into the tables of the parent, after its own members. Nested members are
+/// called on the nested AddIn, their names get the prefix
+/// # Fields
+/// * `prefix` - prefix of English names of nested members, can be empty
+/// * `prefix_ru` - prefix of Russian names of nested members, can be empty
+/// * `get` - function returning the nested AddIn
+/// * `get_mut` - function returning the nested AddIn mutably
+pub struct FlattenDescriptor<T> {
+    pub prefix: &'static str,
+    pub prefix_ru: &'static str,
+    pub get: fn(&T) -> &dyn AddInWrapper,
+    pub get_mut: fn(&mut T) -> &mut dyn AddInWrapper,
+}
+
+impl<T> FlattenDescriptor<T> {
+    fn prefix(&self, alias: usize) -> &'static str {
+        match alias {
+            0 => self.prefix,
+            _ => self.prefix_ru,
+        }
+    }
+}
+
+/// Error of the AddIn method call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MethodError {
+    /// Argument received from 1C can't be converted to the expected type,
+    /// error is reported to 1C with the method and argument name
+    /// # Fields
+    /// * `index` - index of the argument, starting from 0
+    /// * `name` - name of the argument, set with `#[arg(name = ...)]`
+    /// * `error` - conversion error
+    Argument {
+        index: usize,
+        name: Option<&'static str>,
+        error: ConversionError,
+    },
+    /// Count of values passed by 1C does not match count of parameters
+    ArgumentCount { expected: usize, got: usize },
+    /// 1C passed fewer values, and the parameter has no default value
+    /// # Fields
+    /// * `index` - index of the argument, starting from 0
+    MissingArgument { index: usize },
+    /// Returned value can't be converted to the value passed to 1C
+    ReturnValue(ConversionError),
+    /// Method returned an error
+    Failed,
+}
+
+impl MethodError {
+    /// Creates error of the argument conversion
+    pub fn argument(
+        index: usize,
+        name: Option<&'static str>,
+        error: ConversionError,
+    ) -> Self {
+        Self::Argument { index, name, error }
+    }
+}
+
+impl Display for MethodError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Argument {
+                index,
+                name: Some(name),
+                error,
+            } => write!(f, "argument {} ({name}) {error}", index + 1),
+            Self::Argument {
+                index,
+                name: None,
+                error,
+            } => write!(f, "argument {} {error}", index + 1),
+            Self::ArgumentCount { expected, got } => {
+                write!(f, "expected {expected} arguments, got {got}")
+            }
+            Self::MissingArgument { index } => {
+                write!(f, "argument {} is missing", index + 1)
+            }
+            Self::ReturnValue(error) => write!(f, "returned value {error}"),
+            Self::Failed => write!(f, "method failed"),
+        }
+    }
+}
+
+impl std::error::Error for MethodError {}
+
+/// `AddInDescriptor` trait describes the AddIn with static tables of
+/// its properties and methods. Every type implementing it gets generic
+/// `AddInWrapper` implementation, that dispatches 1C calls over these tables.
+///
+/// It is implemented by `#[derive(AddIn)]`, but can also be implemented
+/// manually, when tables have to be written by hand.
+pub trait AddInDescriptor: Sized + 'static {
+    /// Name of the AddIn as it will be shown in 1C platform,
+    /// in UTF-16 with null-terminator
+    const EXTENSION_NAME: &'static [u16];
+
+    /// Properties of the AddIn, index in the table is the property number
+    const PROPS: &'static [PropDescriptor<Self>];
+
+    /// Methods of the AddIn, index in the table is the method number
+    const METHODS: &'static [MethodDescriptor<Self>];
+
+    /// Nested AddIns, their properties and methods are numbered after
+    /// `PROPS` and `METHODS`, in the order of the table. Nested AddIns are
+    /// initialized before the parent, and unloaded after it
+    const FLATTENED: &'static [FlattenDescriptor<Self>] = &[];
+
+    /// Called when the AddIn is loaded by 1C platform, see `AddInWrapper::init`
+    fn init(&mut self, interface: &'static Connection) -> bool;
+
+    /// Connection to 1C platform, if the AddIn stores it. Used to report
+    /// errors of method calls, e.g. wrong argument types
+    fn connection(&self) -> Option<&'static Connection> {
+        None
+    }
+
+    /// Called when the AddIn is unloaded by 1C platform, see `AddInWrapper::done`
+    fn done(&mut self) {}
+
+    /// See `AddInWrapper::set_locale`
+    fn set_locale(&mut self, _loc: &[u16]) {}
+
+    /// See `AddInWrapper::set_user_interface_language_code`
+    fn set_user_interface_language_code(&mut self, _lang: &[u16]) {}
+}
+
+/// Marker of the AddIn, that takes its methods from `AddInMethods`.
+/// Implemented by `#[derive(AddIn)]` for structs with
+/// `#[add_in(impl_methods)]`, so that `#[add_in_methods]` impl block of
+/// other structs fails to compile, instead of being ignored
+#[diagnostic::on_unimplemented(
+    message = "methods of `#[add_in_methods]` impl block are not used by `{Self}`",
+    note = "add `#[add_in(impl_methods)]` to the struct"
+)]
+pub trait ImplMethods {}
+
+/// Methods of the AddIn, declared in `impl` block. Implemented by
+/// `#[add_in_methods]`, and used by `#[derive(AddIn)]` as
+/// `AddInDescriptor::METHODS`, when the struct has `#[add_in(impl_methods)]`
+pub trait AddInMethods: ImplMethods + Sized + 'static {
+    /// Methods of the AddIn, index in the table is the method number
+    const METHODS: &'static [MethodDescriptor<Self>];
+}
+
+/// Letters, allowed in names: Latin, Greek and Cyrillic alphabets, as
+/// `(first, last)` ranges of code points
+const NAME_LETTERS: &[(u32, u32)] = &[
+    (0x0041, 0x005A),
+    (0x0061, 0x007A),
+    (0x00C0, 0x00D6),
+    (0x00D8, 0x00F6),
+    (0x00F8, 0x017F),
+    (0x0386, 0x0386),
+    (0x0388, 0x038A),
+    (0x038C, 0x038C),
+    (0x038E, 0x03A1),
+    (0x03A3, 0x03CE),
+    (0x0400, 0x0481),
+    (0x048A, 0x052F),
+];
+
+/// Letters of `NAME_LETTERS`, that are different from their case folding,
+/// as `(first, last, step, offset)`: every `step`-th code point from `first`
+/// to `last` is folded to the letter `offset` code points after it. These
+/// are uppercase letters, and also `ſ` and final `ς`
+const FOLDED_LETTERS: &[(u32, u32, u32, i32)] = &[
+    (0x0041, 0x005A, 1, 32),
+    (0x00C0, 0x00D6, 1, 32),
+    (0x00D8, 0x00DE, 1, 32),
+    (0x0100, 0x012E, 2, 1),
+    (0x0132, 0x0136, 2, 1),
+    (0x0139, 0x0147, 2, 1),
+    (0x014A, 0x0176, 2, 1),
+    (0x0178, 0x0178, 1, -121),
+    (0x0179, 0x017D, 2, 1),
+    (0x017F, 0x017F, 1, -268),
+    (0x0386, 0x0386, 1, 38),
+    (0x0388, 0x038A, 1, 37),
+    (0x038C, 0x038C, 1, 64),
+    (0x038E, 0x038F, 1, 63),
+    (0x0391, 0x03A1, 1, 32),
+    (0x03A3, 0x03AB, 1, 32),
+    (0x03C2, 0x03C2, 1, 1),
+    (0x0400, 0x040F, 1, 80),
+    (0x0410, 0x042F, 1, 32),
+    (0x0460, 0x0480, 2, 1),
+    (0x048A, 0x04BE, 2, 1),
+    (0x04C0, 0x04C0, 1, 15),
+    (0x04C1, 0x04CD, 2, 1),
+    (0x04D0, 0x052E, 2, 1),
+];
+
+/// Decodes character of valid UTF-8 string, starting at `i`
+/// # Returns
+/// `(u32, usize)` - code point and length of the character in bytes
+const fn decode_char(bytes: &[u8], i: usize) -> (u32, usize) {
+    let first = bytes[i] as u32;
+    let (mut code, len) = match first {
+        0x00..=0x7F => return (first, 1),
+        0xC0..=0xDF => (first & 0x1F, 2),
+        0xE0..=0xEF => (first & 0x0F, 3),
+        _ => (first & 0x07, 4),
+    };
+    let mut j = 1;
+    while j < len {
+        code = (code << 6) | (bytes[i + j] as u32 & 0x3F);
+        j += 1;
+    }
+    (code, len)
+}
+
+const fn is_name_letter(code: u32) -> bool {
+    let mut i = 0;
+    while i < NAME_LETTERS.len() {
+        let (first, last) = NAME_LETTERS[i];
+        if code >= first && code <= last {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn fold_case(code: u32) -> u32 {
+    let mut i = 0;
+    while i < FOLDED_LETTERS.len() {
+        let (first, last, step, offset) = FOLDED_LETTERS[i];
+        if code >= first && code <= last && (code - first).is_multiple_of(step)
+        {
+            return code.wrapping_add_signed(offset);
+        }
+        i += 1;
+    }
+    code
+}
+
+/// Checks that `name` can be used as property or method name in 1C: it is
+/// non-empty, doesn't start with a digit and contains only letters of
+/// Latin, Greek and Cyrillic alphabets, ASCII digits and `_`. Used by
+/// `#[derive(AddIn)]` to check names, set with constants
+pub const fn is_valid_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    if bytes.is_empty() || bytes[0].is_ascii_digit() {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        let (code, len) = decode_char(bytes, i);
+        let is_digit = code >= b'0' as u32 && code <= b'9' as u32;
+        if !(is_name_letter(code) || is_digit || code == b'_' as u32) {
+            return false;
+        }
+        i += len;
+    }
+    true
+}
+
+/// Checks if names are the same for 1C, comparing letters, allowed by
+/// `is_valid_name`, with Unicode case folding, e.g. `Имя` and `ИМЯ`. Used by
+/// `#[derive(AddIn)]` to find duplicate names, set with constants
+pub const fn names_equal(a: &str, b: &str) -> bool {
+    prefixed_names_equal(["", a], ["", b])
+}
+
+/// Same as `names_equal`, for names given as `[prefix, name]` pairs
+const fn prefixed_names_equal(a: [&str; 2], b: [&str; 2]) -> bool {
+    let (a_len, b_len) = (a[0].len() + a[1].len(), b[0].len() + b[1].len());
+    let (mut i, mut j) = (0, 0);
+    while i < a_len && j < b_len {
+        let (code_a, len_a) = decode_prefixed_char(a, i);
+        let (code_b, len_b) = decode_prefixed_char(b, j);
+        if fold_case(code_a) != fold_case(code_b) {
+            return false;
+        }
+        i += len_a;
+        j += len_b;
+    }
+    i == a_len && j == b_len
+}
+
+/// Decodes character of the name, given as `[prefix, name]`, starting at
+/// `i` of them joined, see `decode_char`
+const fn decode_prefixed_char(name: [&str; 2], i: usize) -> (u32, usize) {
+    let prefix = name[0].as_bytes();
+    if i < prefix.len() {
+        decode_char(prefix, i)
+    } else {
+        decode_char(name[1].as_bytes(), i - prefix.len())
+    }
+}
+
+/// English and Russian names of member `i` of the AddIn, properties are
+/// numbered before methods
+const fn member_names<T: AddInDescriptor>(i: usize) -> [&'static str; 2] {
+    if i < T::PROPS.len() {
+        [T::PROPS[i].name, T::PROPS[i].name_ru]
+    } else {
+        let method = &T::METHODS[i - T::PROPS.len()];
+        [method.name, method.name_ru]
+    }
+}
+
+/// Checks if properties and methods of AddIns `A` and `B` have the same
+/// names, see `names_equal`, when `prefixes_a` and `prefixes_b` are added to
+/// them, as done for nested AddIns, see `FlattenDescriptor`. Used by
+/// `#[derive(AddIn)]` to compare own members with nested ones, with empty
+/// prefixes, and nested AddIns with each other. Members of AddIns, nested
+/// into `A` and `B`, are not compared
+pub const fn members_overlap<A: AddInDescriptor, B: AddInDescriptor>(
+    prefixes_a: [&str; 2],
+    prefixes_b: [&str; 2],
+) -> bool {
+    let a_count = A::PROPS.len() + A::METHODS.len();
+    let b_count = B::PROPS.len() + B::METHODS.len();
+    // each member is found by 2 prefixes and 2 names, that are combined
+    // from the bits of the index
+    let mut i = 0;
+    while i < a_count * 4 {
+        let name_a = [prefixes_a[i % 2], member_names::<A>(i / 4)[i / 2 % 2]];
+        let mut j = 0;
+        while j < b_count * 4 {
+            let name_b =
+                [prefixes_b[j % 2], member_names::<B>(j / 4)[j / 2 % 2]];
+            if prefixed_names_equal(name_a, name_b) {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Checks if any of `methods` has `name` in English or Russian, see
+/// `names_equal`. Used by `#[derive(AddIn)]` to check that properties
+/// don't reuse names of methods, declared with `#[add_in_methods]`
+pub const fn methods_have_name<T>(
+    methods: &[MethodDescriptor<T>],
+    name: &str,
+) -> bool {
+    let mut i = 0;
+    while i < methods.len() {
+        if names_equal(methods[i].name, name)
+            || names_equal(methods[i].name_ru, name)
+        {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Registers the profile, where `#[add_in_setting]` fields are stored, see
+/// `Connection::register_profile_as`. Failure is reported to 1C, settings
+/// get default values then. Used by `#[derive(AddIn)]` in `init`
+/// # Returns
+/// `bool` - if the profile is registered and settings can be read
+pub fn register_settings_profile(
+    connection: &'static Connection,
+    extension_name: &[u16],
+    profile: &str,
+) -> bool {
+    let registered = connection.register_profile_as(profile);
+    if !registered {
+        connection.add_error(
+            MessageCode::Attention,
+            &from_os_string(extension_name),
+            &format!(
+                "profile `{profile}` is not available, settings are not loaded"
+            ),
+        );
+    }
+    registered
+}
+
+/// Reads value of the `#[add_in_setting]` field from the profile. Value of
+/// unexpected type, e.g. stored before the type of the field has changed, is
+/// reported to 1C and ignored. Used by `#[derive(AddIn)]` in `init`
+/// # Returns
+/// `Option<T>` - stored value, `None` if there is no value of type `T`
+pub fn read_setting<T: FromParamValue>(
+    connection: &'static Connection,
+    extension_name: &[u16],
+    key: &str,
+) -> Option<T> {
+    let value = connection.read(key)?;
+    T::from_param_value(&value, Coercion::default())
+        .map_err(|err| {
+            connection.add_error(
+                MessageCode::Attention,
+                &from_os_string(extension_name),
+                &format!("setting `{key}` is ignored: {err}"),
+            )
+        })
+        .ok()
+}
+
+/// Checks if `name`, requested by 1C, is one of the names of the member. 1C
+/// language ignores case, so names are compared as in `names_equal`
+pub(super) fn matches_name(name: &str, name_en: &str, name_ru: &str) -> bool {
+    names_equal(name, name_en) || names_equal(name, name_ru)
+}
+
+/// Removes `prefix` from the start of `name`, ignoring case as in
+/// `names_equal`
+fn strip_name_prefix<'a>(name: &'a str, prefix: &str) -> Option<&'a str> {
+    let mut chars = name.chars();
+    for prefix_char in prefix.chars() {
+        let name_char = chars.next()?;
+        if fold_case(name_char as u32) != fold_case(prefix_char as u32) {
+            return None;
+        }
+    }
+    Some(chars.as_str())
+}
+
+fn alias_name(alias: usize, name_en: &str, name_ru: &str) -> Vec<u16> {
+    match alias {
+        0 => os_string_nil(name_en),
+        _ => os_string_nil(name_ru),
+    }
+}
+
+/// Checks count of values passed by 1C, and fills missing ones with
+/// default values of the parameters
+/// # Arguments
+/// * `expected` - count of parameters of the method
+/// * `default` - returns default value of the parameter by its index
+pub(super) fn fill_params(
+    params: &mut ParamValues,
+    expected: usize,
+    default: impl Fn(usize) -> Option<ParamValue>,
+) -> Result<(), MethodError> {
+    if params.len() > expected {
+        return Err(MethodError::ArgumentCount {
+            expected,
+            got: params.len(),
+        });
+    }
+    for index in params.len()..expected {
+        let default =
+            default(index).ok_or(MethodError::MissingArgument { index })?;
+        params.values.push(default);
+    }
+    Ok(())
+}
+
+/// Reports error of the method call to 1C, so that it is raised as exception
+/// with a description, e.g.
+/// "MyFunction: argument 2 (Amount) expected Number, got String".
+/// `MethodError::Failed` is not reported, as the method has no description
+pub(super) fn report_method_error(
+    connection: Option<&'static Connection>,
+    extension_name: &[u16],
+    method_name: &str,
+    err: &MethodError,
+) {
+    if *err == MethodError::Failed {
+        return;
+    }
+    report_error(connection, extension_name, method_name, err);
+}
+
+/// Reports error of the member to 1C as exception with `Name: error`
+/// description
+pub(super) fn report_error(
+    connection: Option<&'static Connection>,
+    extension_name: &[u16],
+    member_name: &str,
+    err: &dyn Display,
+) {
+    let Some(connection) = connection else {
+        return;
+    };
+    connection.add_error(
+        MessageCode::Fail,
+        &from_os_string(extension_name),
+        &format!("{member_name}: {err}"),
+    );
+}
+
+/// Reports error of the property getter or setter to 1C, so that it is
+/// raised as exception with a description, e.g. "Port: port is busy".
+/// Used by `#[derive(AddIn)]` for conversion errors of written values and
+/// errors of `get` and `set` functions with `result`
+pub fn report_prop_error<T: AddInDescriptor>(
+    addin: &T,
+    prop_name: &str,
+    err: &dyn Display,
+) {
+    report_error(addin.connection(), T::EXTENSION_NAME, prop_name, err);
+}
+
+/// Calls the method and reports its errors to 1C
+fn call_method<T: AddInDescriptor>(
+    addin: &mut T,
+    method: &MethodDescriptor<T>,
+    params: &mut ParamValues,
+) -> AddInWrapperResult<ParamValue> {
+    fill_params(params, method.params.len(), |index| {
+        method.params[index].default.map(|default| default())
+    })
+    .and_then(|_| (method.call)(addin, params))
+    .map_err(|err| {
+        report_method_error(
+            addin.connection(),
+            T::EXTENSION_NAME,
+            method.name,
+            &err,
+        )
+    })
+}
+
+/// Finds the nested AddIn, that has member `num` of the merged table, and
+/// number of the member in it
+/// # Arguments
+/// * `own` - count of own members of the AddIn, numbered first
+/// * `count` - returns count of members of the nested AddIn
+fn find_nested<T: AddInDescriptor>(
+    addin: &T,
+    num: usize,
+    own: usize,
+    count: fn(&dyn AddInWrapper) -> usize,
+) -> Option<(&'static FlattenDescriptor<T>, usize)> {
+    let mut num = num.checked_sub(own)?;
+    for flatten in T::FLATTENED {
+        let nested_count = count((flatten.get)(addin));
+        if num < nested_count {
+            return Some((flatten, num));
+        }
+        num -= nested_count;
+    }
+    None
+}
+
+/// Finds number of the nested member by its name with prefix
+/// # Arguments
+/// * `own` - count of own members of the AddIn, numbered first
+/// * `count` - returns count of members of the nested AddIn
+/// * `find` - finds the member of the nested AddIn by its name
+fn find_nested_by_name<T: AddInDescriptor>(
+    addin: &T,
+    name: &[u16],
+    own: usize,
+    count: fn(&dyn AddInWrapper) -> usize,
+    find: fn(&dyn AddInWrapper, &[u16]) -> Option<usize>,
+) -> Option<usize> {
+    let name = from_os_string(name);
+    let mut offset = own;
+    for flatten in T::FLATTENED {
+        let nested = (flatten.get)(addin);
+        for prefix in [flatten.prefix, flatten.prefix_ru] {
+            let found = strip_name_prefix(&name, prefix)
+                .and_then(|name| find(nested, &os_string(name)));
+            if let Some(num) = found {
+                return Some(offset + num);
+            }
+        }
+        offset += count(nested);
+    }
+    None
+}
+
+fn prefixed_name(prefix: &str, name: Vec<u16>) -> Vec<u16> {
+    let mut prefixed = os_string(prefix);
+    prefixed.extend(name);
+    prefixed
+}
+
+fn nested_props(nested: &dyn AddInWrapper) -> usize {
+    nested.get_n_props()
+}
+
+fn nested_methods(nested: &dyn AddInWrapper) -> usize {
+    nested.get_n_methods()
+}
+
+impl<T: AddInDescriptor> AddInWrapper for T {
+    fn init(&mut self, interface: &'static Connection) -> bool {
+        for flatten in T::FLATTENED {
+            if !(flatten.get_mut)(self).init(interface) {
+                return false;
+            }
+        }
+        AddInDescriptor::init(self, interface)
+    }
+
+    fn done(&mut self) {
+        AddInDescriptor::done(self);
+        for flatten in T::FLATTENED {
+            (flatten.get_mut)(self).done();
+        }
+    }
+
+    fn register_extension_as(&mut self) -> &[u16] {
+        T::EXTENSION_NAME
+    }
+
+    fn get_n_props(&self) -> usize {
+        let nested = T::FLATTENED
+            .iter()
+            .map(|flatten| nested_props((flatten.get)(self)));
+        T::PROPS.len() + nested.sum::<usize>()
+    }
+
+    fn find_prop(&self, name: &[u16]) -> Option<usize> {
+        let name_str = from_os_string(name);
+        T::PROPS
+            .iter()
+            .position(|prop| matches_name(&name_str, prop.name, prop.name_ru))
+            .or_else(|| {
+                find_nested_by_name(
+                    self,
+                    name,
+                    T::PROPS.len(),
+                    nested_props,
+                    |nested, name| nested.find_prop(name),
+                )
+            })
+    }
+
+    fn get_prop_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        if let Some(prop) = T::PROPS.get(num) {
+            return Some(alias_name(alias, prop.name, prop.name_ru));
+        }
+        let (flatten, num) =
+            find_nested(self, num, T::PROPS.len(), nested_props)?;
+        let name = (flatten.get)(self).get_prop_name(num, alias)?;
+        Some(prefixed_name(flatten.prefix(alias), name))
+    }
+
+    fn get_prop_val(&self, num: usize) -> AddInWrapperResult<ParamValue> {
+        let Some(prop) = T::PROPS.get(num) else {
+            let (flatten, num) =
+                find_nested(self, num, T::PROPS.len(), nested_props)
+                    .ok_or(())?;
+            return (flatten.get)(self).get_prop_val(num);
+        };
+        let getter = prop.getter.ok_or(())?;
+        getter(self)
+    }
+
+    fn set_prop_val(
+        &mut self,
+        num: usize,
+        val: ParamValue,
+    ) -> AddInWrapperResult<()> {
+        let Some(prop) = T::PROPS.get(num) else {
+            let (flatten, num) =
+                find_nested(self, num, T::PROPS.len(), nested_props)
+                    .ok_or(())?;
+            return (flatten.get_mut)(self).set_prop_val(num, val);
+        };
+        let setter = prop.setter.ok_or(())?;
+        setter(self, val)
+    }
+
+    fn is_prop_readable(&self, num: usize) -> bool {
+        let Some(prop) = T::PROPS.get(num) else {
+            return find_nested(self, num, T::PROPS.len(), nested_props)
+                .is_some_and(|(flatten, num)| {
+                    (flatten.get)(self).is_prop_readable(num)
+                });
+        };
+        prop.getter.is_some()
+    }
+
+    fn is_prop_writable(&self, num: usize) -> bool {
+        let Some(prop) = T::PROPS.get(num) else {
+            return find_nested(self, num, T::PROPS.len(), nested_props)
+                .is_some_and(|(flatten, num)| {
+                    (flatten.get)(self).is_prop_writable(num)
+                });
+        };
+        prop.setter.is_some()
+    }
+
+    fn get_n_methods(&self) -> usize {
+        let nested = T::FLATTENED
+            .iter()
+            .map(|flatten| nested_methods((flatten.get)(self)));
+        T::METHODS.len() + nested.sum::<usize>()
+    }
+
+    fn find_method(&self, name: &[u16]) -> Option<usize> {
+        let name_str = from_os_string(name);
+        T::METHODS
+            .iter()
+            .position(|method| {
+                matches_name(&name_str, method.name, method.name_ru)
+            })
+            .or_else(|| {
+                find_nested_by_name(
+                    self,
+                    name,
+                    T::METHODS.len(),
+                    nested_methods,
+                    |nested, name| nested.find_method(name),
+                )
+            })
+    }
+
+    fn get_method_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        if let Some(method) = T::METHODS.get(num) {
+            return Some(alias_name(alias, method.name, method.name_ru));
+        }
+        let (flatten, num) =
+            find_nested(self, num, T::METHODS.len(), nested_methods)?;
+        let name = (flatten.get)(self).get_method_name(num, alias)?;
+        Some(prefixed_name(flatten.prefix(alias), name))
+    }
+
+    fn get_n_params(&self, num: usize) -> usize {
+        let Some(method) = T::METHODS.get(num) else {
+            return find_nested(self, num, T::METHODS.len(), nested_methods)
+                .map_or(0, |(flatten, num)| {
+                    (flatten.get)(self).get_n_params(num)
+                });
+        };
+        method.params.len()
+    }
+
+    fn get_param_def_value(
+        &self,
+        method_num: usize,
+        param_num: usize,
+    ) -> Option<ParamValue> {
+        let Some(method) = T::METHODS.get(method_num) else {
+            let (flatten, method_num) = find_nested(
+                self,
+                method_num,
+                T::METHODS.len(),
+                nested_methods,
+            )?;
+            return (flatten.get)(self)
+                .get_param_def_value(method_num, param_num);
+        };
+        let param = method.params.get(param_num)?;
+        param.default.map(|default| default())
+    }
+
+    fn has_ret_val(&self, method_num: usize) -> bool {
+        let Some(method) = T::METHODS.get(method_num) else {
+            return find_nested(
+                self,
+                method_num,
+                T::METHODS.len(),
+                nested_methods,
+            )
+            .is_some_and(|(flatten, num)| {
+                (flatten.get)(self).has_ret_val(num)
+            });
+        };
+        method.has_ret_val
+    }
+
+    fn call_as_proc(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<()> {
+        let Some(method) = T::METHODS.get(method_num) else {
+            let (flatten, method_num) =
+                find_nested(self, method_num, T::METHODS.len(), nested_methods)
+                    .ok_or(())?;
+            return (flatten.get_mut)(self).call_as_proc(method_num, params);
+        };
+        call_method(self, method, params).map(|_| ())
+    }
+
+    fn call_as_func(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue> {
+        let Some(method) = T::METHODS.get(method_num) else {
+            let (flatten, method_num) =
+                find_nested(self, method_num, T::METHODS.len(), nested_methods)
+                    .ok_or(())?;
+            return (flatten.get_mut)(self).call_as_func(method_num, params);
+        };
+        if !method.has_ret_val {
+            return Err(());
+        }
+        call_method(self, method, params)
+    }
+
+    fn set_locale(&mut self, loc: &[u16]) {
+        AddInDescriptor::set_locale(self, loc);
+        for flatten in T::FLATTENED {
+            (flatten.get_mut)(self).set_locale(loc);
+        }
+    }
+
+    fn set_user_interface_language_code(&mut self, lang: &[u16]) {
+        AddInDescriptor::set_user_interface_language_code(self, lang);
+        for flatten in T::FLATTENED {
+            (flatten.get_mut)(self).set_user_interface_language_code(lang);
+        }
+    }
+}
@@ -0,0 +1,405 @@
+use std::cell::RefCell;
+
+use super::{
+    descriptors::{
+        fill_params, matches_name, report_error, report_method_error,
+        MethodError,
+    },
+    AddInWrapper, AddInWrapperResult, ConnectionField, ConnectionHandle,
+    ParamValue, ParamValues,
+};
+use crate::ffi::{
+    connection::Connection,
+    string_utils::{from_os_string, os_string, os_string_nil},
+};
+
+type MethodFn =
+    Box<dyn FnMut(&mut ParamValues) -> Result<ParamValue, MethodError>>;
+type GetterFn = Box<dyn Fn() -> Result<ParamValue, String>>;
+type SetterFn = Box<dyn FnMut(ParamValue) -> Result<(), String>>;
+type FallbackFn = Box<dyn Fn(&str) -> Option<DynamicMethod>>;
+
+/// Method of `DynamicAddIn`, returned by the fallback handler for names,
+/// that are not registered with `DynamicAddIn::method`
+pub struct DynamicMethod {
+    params: Vec<Option<ParamValue>>,
+    has_ret_val: bool,
+    call: MethodFn,
+}
+
+impl DynamicMethod {
+    /// Creates method, that returns a value
+    /// # Arguments
+    /// * `params` - default values of parameters, `None` for required ones
+    /// * `call` - called with values of all parameters, missing ones are
+    ///   filled with defaults
+    pub fn function(
+        params: Vec<Option<ParamValue>>,
+        call: impl FnMut(&mut ParamValues) -> Result<ParamValue, MethodError>
+            + 'static,
+    ) -> Self {
+        Self {
+            params,
+            has_ret_val: true,
+            call: Box::new(call),
+        }
+    }
+
+    /// Creates method without return value
+    /// # Arguments
+    /// * `params` - default values of parameters, `None` for required ones
+    /// * `call` - called with values of all parameters, missing ones are
+    ///   filled with defaults
+    pub fn procedure(
+        params: Vec<Option<ParamValue>>,
+        mut call: impl FnMut(&mut ParamValues) -> Result<(), MethodError> + 'static,
+    ) -> Self {
+        Self {
+            params,
+            has_ret_val: false,
+            call: Box::new(move |params| {
+                call(params).map(|_| ParamValue::Empty)
+            }),
+        }
+    }
+}
+
+struct NamedMethod {
+    name: String,
+    name_ru: String,
+    method: DynamicMethod,
+}
+
+struct DynamicProp {
+    name: String,
+    name_ru: String,
+    getter: GetterFn,
+    setter: Option<SetterFn>,
+}
+
+/// AddIn, whose properties and methods are registered at runtime, e.g. read
+/// from a configuration file. It is built with chained calls, and is passed
+/// to `create_component` or `extern_functions!` like a derived AddIn:
+/// ```ignore
+/// DynamicAddIn::new("Queries")
+///     .property("Version", "Версия", || Ok(ParamValue::I32(1)))
+///     .method("Run", "Выполнить", vec![None], |params| {
+///         Ok(params[0].clone())
+///     })
+/// ```
+/// Errors of method calls and of property getters and setters are reported to
+/// 1C as for derived AddIns
+pub struct DynamicAddIn {
+    extension_name: Vec<u16>,
+    props: Vec<DynamicProp>,
+    // methods resolved by the fallback are added in `find_method`
+    methods: RefCell<Vec<NamedMethod>>,
+    fallback: Option<FallbackFn>,
+    connection: ConnectionHandle,
+}
+
+impl DynamicAddIn {
+    /// Creates AddIn without properties and methods
+    /// # Arguments
+    /// * `name` - name of the AddIn as it will be shown in 1C platform
+    pub fn new(name: &str) -> Self {
+        let mut extension_name = os_string(name);
+        extension_name.push(0);
+        Self {
+            extension_name,
+            props: vec![],
+            methods: RefCell::new(vec![]),
+            fallback: None,
+            connection: ConnectionHandle::new(),
+        }
+    }
+
+    /// Sets handle, that receives connection to 1C platform on init. Its
+    /// clones can be captured by methods, e.g. to send external events
+    pub fn connection_handle(mut self, handle: ConnectionHandle) -> Self {
+        self.connection = handle;
+        self
+    }
+
+    /// Adds readable property
+    /// # Arguments
+    /// * `name` - property name in 1C
+    /// * `name_ru` - property name in 1C in Russian
+    /// * `getter` - returns value of the property, or description of the
+    ///   error, reported to 1C
+    pub fn property(
+        mut self,
+        name: &str,
+        name_ru: &str,
+        getter: impl Fn() -> Result<ParamValue, String> + 'static,
+    ) -> Self {
+        self.props.push(DynamicProp {
+            name: name.to_string(),
+            name_ru: name_ru.to_string(),
+            getter: Box::new(getter),
+            setter: None,
+        });
+        self
+    }
+
+    /// Adds readable and writable property
+    /// # Arguments
+    /// * `name` - property name in 1C
+    /// * `name_ru` - property name in 1C in Russian
+    /// * `getter` - returns value of the property, or description of the
+    ///   error, reported to 1C
+    /// * `setter` - receives value of the property, set by 1C, returns
+    ///   description of the error, e.g. for a value of a wrong type
+    pub fn writable_property(
+        mut self,
+        name: &str,
+        name_ru: &str,
+        getter: impl Fn() -> Result<ParamValue, String> + 'static,
+        setter: impl FnMut(ParamValue) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.props.push(DynamicProp {
+            name: name.to_string(),
+            name_ru: name_ru.to_string(),
+            getter: Box::new(getter),
+            setter: Some(Box::new(setter)),
+        });
+        self
+    }
+
+    /// Adds method, that returns a value, see `DynamicMethod::function`
+    /// # Arguments
+    /// * `name` - method name in 1C
+    /// * `name_ru` - method name in 1C in Russian
+    /// * `params` - default values of parameters, `None` for required ones
+    /// * `call` - called with values of all parameters
+    pub fn method(
+        self,
+        name: &str,
+        name_ru: &str,
+        params: Vec<Option<ParamValue>>,
+        call: impl FnMut(&mut ParamValues) -> Result<ParamValue, MethodError>
+            + 'static,
+    ) -> Self {
+        self.add_method(name, name_ru, DynamicMethod::function(params, call))
+    }
+
+    /// Adds method without return value, see `DynamicMethod::procedure`
+    /// # Arguments
+    /// * `name` - method name in 1C
+    /// * `name_ru` - method name in 1C in Russian
+    /// * `params` - default values of parameters, `None` for required ones
+    /// * `call` - called with values of all parameters
+    pub fn procedure(
+        self,
+        name: &str,
+        name_ru: &str,
+        params: Vec<Option<ParamValue>>,
+        call: impl FnMut(&mut ParamValues) -> Result<(), MethodError> + 'static,
+    ) -> Self {
+        self.add_method(name, name_ru, DynamicMethod::procedure(params, call))
+    }
+
+    /// Sets handler for names of methods, that are not registered. Method,
+    /// returned by the handler, is added to the AddIn under the requested
+    /// name, so later lookups of the name return the same number, and
+    /// `get_n_methods` grows with each resolved name. Names, for which the
+    /// handler returns `None`, are not added
+    /// # Arguments
+    /// * `fallback` - receives name of the method, returns `None` if there is
+    ///   no such method
+    pub fn fallback(
+        mut self,
+        fallback: impl Fn(&str) -> Option<DynamicMethod> + 'static,
+    ) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    fn add_method(
+        mut self,
+        name: &str,
+        name_ru: &str,
+        method: DynamicMethod,
+    ) -> Self {
+        self.methods.get_mut().push(NamedMethod {
+            name: name.to_string(),
+            name_ru: name_ru.to_string(),
+            method,
+        });
+        self
+    }
+
+    fn call_method(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue> {
+        let connection = self.connection.get();
+        let methods = self.methods.get_mut();
+        let named = methods.get_mut(method_num).ok_or(())?;
+        let method = &mut named.method;
+        fill_params(params, method.params.len(), |index| {
+            method.params[index].clone()
+        })
+        .and_then(|_| (method.call)(params))
+        .map_err(|err| {
+            report_method_error(
+                connection,
+                &self.extension_name,
+                &named.name,
+                &err,
+            )
+        })
+    }
+}
+
+fn alias_name(alias: usize, name: &str, name_ru: &str) -> Vec<u16> {
+    match alias {
+        0 => os_string_nil(name),
+        _ => os_string_nil(name_ru),
+    }
+}
+
+impl AddInWrapper for DynamicAddIn {
+    fn init(&mut self, interface: &'static Connection) -> bool {
+        self.connection.set_connection(interface);
+        true
+    }
+
+    fn done(&mut self) {}
+
+    fn register_extension_as(&mut self) -> &[u16] {
+        &self.extension_name
+    }
+
+    fn get_n_props(&self) -> usize {
+        self.props.len()
+    }
+
+    fn find_prop(&self, name: &[u16]) -> Option<usize> {
+        let name = from_os_string(name);
+        self.props
+            .iter()
+            .position(|prop| matches_name(&name, &prop.name, &prop.name_ru))
+    }
+
+    fn get_prop_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        let prop = self.props.get(num)?;
+        Some(alias_name(alias, &prop.name, &prop.name_ru))
+    }
+
+    fn get_prop_val(&self, num: usize) -> AddInWrapperResult<ParamValue> {
+        let prop = self.props.get(num).ok_or(())?;
+        (prop.getter)().map_err(|err| {
+            report_error(
+                self.connection.get(),
+                &self.extension_name,
+                &prop.name,
+                &err,
+            )
+        })
+    }
+
+    fn set_prop_val(
+        &mut self,
+        num: usize,
+        val: ParamValue,
+    ) -> AddInWrapperResult<()> {
+        let connection = self.connection.get();
+        let prop = self.props.get_mut(num).ok_or(())?;
+        let setter = prop.setter.as_mut().ok_or(())?;
+        setter(val).map_err(|err| {
+            report_error(connection, &self.extension_name, &prop.name, &err)
+        })
+    }
+
+    fn is_prop_readable(&self, num: usize) -> bool {
+        num < self.props.len()
+    }
+
+    fn is_prop_writable(&self, num: usize) -> bool {
+        self.props
+            .get(num)
+            .is_some_and(|prop| prop.setter.is_some())
+    }
+
+    fn get_n_methods(&self) -> usize {
+        self.methods.borrow().len()
+    }
+
+    fn find_method(&self, name: &[u16]) -> Option<usize> {
+        let name = from_os_string(name);
+        let mut methods = self.methods.borrow_mut();
+        let position = methods
+            .iter()
+            .position(|named| matches_name(&name, &named.name, &named.name_ru));
+        if position.is_some() {
+            return position;
+        }
+
+        let method = (self.fallback.as_ref()?)(&name)?;
+        methods.push(NamedMethod {
+            name_ru: name.clone(),
+            name,
+            method,
+        });
+        Some(methods.len() - 1)
+    }
+
+    fn get_method_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        let methods = self.methods.borrow();
+        let named = methods.get(num)?;
+        Some(alias_name(alias, &named.name, &named.name_ru))
+    }
+
+    fn get_n_params(&self, num: usize) -> usize {
+        self.methods
+            .borrow()
+            .get(num)
+            .map_or(0, |named| named.method.params.len())
+    }
+
+    fn get_param_def_value(
+        &self,
+        method_num: usize,
+        param_num: usize,
+    ) -> Option<ParamValue> {
+        let methods = self.methods.borrow();
+        methods
+            .get(method_num)?
+            .method
+            .params
+            .get(param_num)?
+            .clone()
+    }
+
+    fn has_ret_val(&self, method_num: usize) -> bool {
+        self.methods
+            .borrow()
+            .get(method_num)
+            .is_some_and(|named| named.method.has_ret_val)
+    }
+
+    fn call_as_proc(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<()> {
+        self.call_method(method_num, params).map(|_| ())
+    }
+
+    fn call_as_func(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue> {
+        if !self.has_ret_val(method_num) {
+            return Err(());
+        }
+        self.call_method(method_num, params)
+    }
+
+    fn set_locale(&mut self, _loc: &[u16]) {}
+
+    fn set_user_interface_language_code(&mut self, _lang: &[u16]) {}
+}
@@ -0,0 +1,166 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    Coercion, ConversionError, FromParamValue, IntoParamValue, ParamValue,
+};
+use crate::ffi::string_utils::os_string;
+
+/// Value, passed to and from 1C as JSON string, e.g. produced by
+/// `ЗаписатьJSON` and read by `ПрочитатьJSON`
+///
+/// Incoming string is deserialized into `T`, errors contain path to the
+/// invalid field, e.g. "invalid JSON at `items[1].amount`: invalid type:
+/// string \"ten\", expected f64". Returned value is serialized into string,
+/// if serialization fails (e.g. map with non-string keys), the error is
+/// returned by `try_into_param_value` and reported to 1C. Dates are written
+/// by 1C as strings, use [`date`] or [`optional_date`] modules with
+/// `#[serde(with = "...")]` to read and write them in the same format
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    /// Returns wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Json<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: DeserializeOwned> FromParamValue for Json<T> {
+    fn from_param_value(
+        value: &ParamValue,
+        _coercion: Coercion,
+    ) -> Result<Self, ConversionError> {
+        let json = value.as_str().ok_or_else(|| {
+            ConversionError::unexpected_type("String with JSON", value)
+        })?;
+
+        let deserializer = &mut serde_json::Deserializer::from_str(&json);
+        let result: Result<T, _> =
+            serde_path_to_error::deserialize(&mut *deserializer);
+        let value = result.map_err(|err| {
+            let path = err.path().to_string();
+            match path.as_str() {
+                "." => ConversionError::new(format!(
+                    "invalid JSON: {}",
+                    err.inner()
+                )),
+                _ => ConversionError::new(format!(
+                    "invalid JSON at `{path}`: {}",
+                    err.inner()
+                )),
+            }
+        })?;
+        deserializer.end().map_err(|err| {
+            ConversionError::new(format!("invalid JSON: {err}"))
+        })?;
+
+        Ok(Self(value))
+    }
+}
+
+/// `Undefined` is returned, if serialization fails
+impl<T: Serialize> IntoParamValue for Json<T> {
+    fn into_param_value(self) -> ParamValue {
+        self.try_into_param_value().unwrap_or(ParamValue::Empty)
+    }
+
+    fn try_into_param_value(self) -> Result<ParamValue, ConversionError> {
+        let json = serde_json::to_string(&self.0).map_err(|err| {
+            ConversionError::new(format!("can't serialize JSON: {err}"))
+        })?;
+        Ok(ParamValue::String(os_string(&json)))
+    }
+}
+
+/// Format of dates, written by `ЗаписатьJSON` with `ФорматДатыJSON.ISO`
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+fn parse_date(value: &str) -> Result<chrono::NaiveDateTime, String> {
+    if let Ok(date) = chrono::NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+    {
+        return Ok(date);
+    }
+    // `ВариантЗаписиДатыJSON.УниверсальнаяДата` adds `Z` and
+    // `ЛокальнаяДатаСоСмещением` adds offset. 1C dates have no time zone,
+    // and converting them to the local time of the AddIn may not match
+    // the time zone of the 1C session
+    if chrono::DateTime::parse_from_rfc3339(value).is_ok() {
+        return Err(format!(
+            "date `{value}` has time zone, which is not supported, \
+            write dates with `ВариантЗаписиДатыJSON.ЛокальнаяДата`"
+        ));
+    }
+    Err(format!(
+        "invalid date `{value}`, expected `YYYY-MM-DDThh:mm:ss`"
+    ))
+}
+
+/// Serialization of `chrono::NaiveDateTime` in ISO format, used by 1C,
+/// e.g. `2024-01-31T12:00:00`. Dates with `Z` or offset are rejected.
+/// Use as `#[serde(with = "native_api_1c::native_api_1c_core::interface::json::date")]`
+pub mod date {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &chrono::NaiveDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&value.format(super::DATE_FORMAT))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<chrono::NaiveDateTime, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        super::parse_date(&value).map_err(D::Error::custom)
+    }
+}
+
+/// Same as [`date`], but for `Option<chrono::NaiveDateTime>`, where `null`
+/// is `None`
+pub mod optional_date {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<chrono::NaiveDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => super::date::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<chrono::NaiveDateTime>, D::Error> {
+        let Some(value) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        super::parse_date(&value)
+            .map(Some)
+            .map_err(D::Error::custom)
+    }
+}
@@ -1,9 +1,36 @@
 use std::ops::{Index, IndexMut};
 
-use crate::ffi::{connection::Connection, provided_types::Tm};
+use crate::ffi::{
+    connection::Connection, provided_types::Tm, string_utils::from_os_string,
+};
+
+/// Static tables describing AddIn properties and methods, and generic
+/// `AddInWrapper` implementation over them
+pub mod descriptors;
+
+mod coercion;
+mod connection;
+mod convert;
+mod dynamic;
+mod progress;
+pub use coercion::Coercion;
+pub use connection::{ConnectionField, ConnectionHandle};
+pub use convert::{ConversionError, FromParamValue, IntoParamValue};
+pub use dynamic::{DynamicAddIn, DynamicMethod};
+pub use progress::Progress;
+
+/// `Json<T>` wrapper for values passed as JSON strings, and helpers for
+/// dates in 1C format
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "serde")]
+pub use json::Json;
 
 /// Represents 1C variant values for parameters in safe Rust code.
+/// New variants can be added for other 1C types, so `match` on it needs
+/// a wildcard arm
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum ParamValue {
     /// Empty value
     Empty,
@@ -11,6 +38,10 @@ pub enum ParamValue {
     Bool(bool),
     /// Integer value
     I32(i32),
+    /// Integer value, that does not fit into `I32` and can't be
+    /// represented exactly by `F64`, passed to the platform as `VTYPE_I8`.
+    /// Added in 0.10
+    I64(i64),
     /// Float value
     F64(f64),
     /// Date-time value
@@ -21,7 +52,35 @@ pub enum ParamValue {
     Blob(Vec<u8>),
 }
 
+/// Largest integer, that `f64` represents exactly, same as
+/// `Number.MAX_SAFE_INTEGER` in JavaScript
+const MAX_EXACT_F64_INTEGER: u64 = (1 << f64::MANTISSA_DIGITS) - 1;
+
 impl ParamValue {
+    /// Creates number value from `i64` in the narrowest form 1C accepts:
+    /// `I32` if it fits, `F64` if it is represented exactly (up to 2^53 - 1
+    /// by absolute value), `I64` otherwise, so the platform receives values
+    /// from 2^53 as `VTYPE_I8`
+    pub fn from_i64(val: i64) -> Self {
+        if let Ok(val) = i32::try_from(val) {
+            Self::I32(val)
+        } else if val.unsigned_abs() <= MAX_EXACT_F64_INTEGER {
+            Self::F64(val as f64)
+        } else {
+            Self::I64(val)
+        }
+    }
+
+    /// Creates number value from `u64`, see `from_i64`. Values from 2^53 to
+    /// `i64::MAX` are passed as `I64` (`VTYPE_I8`), values above
+    /// `i64::MAX` are passed as `F64` and lose precision
+    pub fn from_u64(val: u64) -> Self {
+        match i64::try_from(val) {
+            Ok(val) => Self::from_i64(val),
+            Err(_) => Self::F64(val as f64),
+        }
+    }
+
     pub fn set_bool(&mut self, val: bool) {
         *self = Self::Bool(val);
     }
@@ -45,6 +104,142 @@ impl ParamValue {
     pub fn set_blob(&mut self, val: Vec<u8>) {
         *self = Self::Blob(val);
     }
+
+    /// Returns name of the value type in 1C, used in error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Empty => "Undefined",
+            Self::Bool(_) => "Boolean",
+            Self::I32(_) | Self::I64(_) | Self::F64(_) => "Number",
+            Self::Date(_) => "Date",
+            Self::String(_) => "String",
+            Self::Blob(_) => "BinaryData",
+        }
+    }
+
+    /// Returns `true` if value is `Empty`, i.e. `Undefined` in 1C
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// Returns `Some` if value is `Bool`
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `I32`
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Self::I32(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is an integer number in `i64` range:
+    /// `I32`, `I64` or `F64` without fractional part, as 1C passes
+    /// numbers, that do not fit into `I32`, as `F64`
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::I32(val) => Some((*val).into()),
+            Self::I64(val) => Some(*val),
+            Self::F64(val)
+                if val.fract() == 0.0
+                    && *val >= i64::MIN as f64
+                    && *val < i64::MAX as f64 =>
+            {
+                Some(*val as i64)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is an integer number in `u64` range,
+    /// see `as_i64`
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::F64(val)
+                if val.fract() == 0.0
+                    && *val >= 0.0
+                    && *val < u64::MAX as f64 =>
+            {
+                Some(*val as u64)
+            }
+            _ => self.as_i64().and_then(|val| u64::try_from(val).ok()),
+        }
+    }
+
+    /// Creates `String` value from `Decimal`, so that 1C gets it without
+    /// rounding. String has `.` as decimal separator and no grouping,
+    /// in 1C it is converted back with `XMLЗначение(Тип("Число"), ...)`
+    #[cfg(feature = "decimal")]
+    pub fn from_decimal(val: rust_decimal::Decimal) -> Self {
+        let val = val.normalize().to_string();
+        Self::String(crate::ffi::string_utils::os_string(&val))
+    }
+
+    /// Returns `Some` if value is a number, or a string with a number,
+    /// written with `.` as decimal separator, e.g. by `XMLСтрока(...)` in 1C.
+    /// `F64` is converted to the shortest decimal representing it.
+    /// Numbers with more than 28 significant digits are not rounded,
+    /// `None` is returned for them
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<rust_decimal::Decimal> {
+        use rust_decimal::Decimal;
+
+        match self {
+            Self::I32(val) => Some((*val).into()),
+            Self::I64(val) => Some((*val).into()),
+            Self::F64(val) => Decimal::try_from(*val).ok(),
+            Self::String(val) => {
+                Decimal::from_str_exact(from_os_string(val).trim()).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `F64`
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::F64(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `Date`
+    pub fn as_date(&self) -> Option<Tm> {
+        match self {
+            Self::Date(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `String`, decoded from UTF-16
+    /// without trailing null-terminator
+    pub fn as_str(&self) -> Option<String> {
+        match self {
+            Self::String(val) => Some(from_os_string(val)),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `String`, as raw UTF-16
+    pub fn as_utf16(&self) -> Option<&[u16]> {
+        match self {
+            Self::String(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some` if value is `Blob`
+    pub fn as_blob(&self) -> Option<&[u8]> {
+        match self {
+            Self::Blob(val) => Some(val),
+            _ => None,
+        }
+    }
 }
 
 impl PartialEq for ParamValue {
@@ -53,6 +248,7 @@ impl PartialEq for ParamValue {
             (Self::Empty, Self::Empty) => true,
             (Self::Bool(a), Self::Bool(b)) => a == b,
             (Self::I32(a), Self::I32(b)) => a == b,
+            (Self::I64(a), Self::I64(b)) => a == b,
             (Self::F64(a), Self::F64(b)) => a == b,
             (Self::Date(a), Self::Date(b)) => a == b,
             (Self::String(a), Self::String(b)) => a == b,
@@ -83,9 +279,19 @@ impl ParamValues {
         self.values.is_empty()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<ParamValue> {
+    pub fn iter(&self) -> std::slice::Iter<'_, ParamValue> {
         self.values.iter()
     }
+
+    /// Returns value at `index`, or `None` if 1C passed fewer values
+    pub fn get(&self, index: usize) -> Option<&ParamValue> {
+        self.values.get(index)
+    }
+
+    /// Returns mutable value at `index`, or `None` if 1C passed fewer values
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut ParamValue> {
+        self.values.get_mut(index)
+    }
 }
 
 impl Index<usize> for ParamValues {
@@ -290,3 +496,107 @@ pub trait AddInWrapper {
     /// * `lang` - language code in UTF-16, two letters
     fn set_user_interface_language_code(&mut self, lang: &[u16]);
 }
+
+/// Boxed AddIn, so that the implementation can be chosen at runtime, e.g.
+/// from a configuration file, and passed to `create_component`
+impl AddInWrapper for Box<dyn AddInWrapper> {
+    fn init(&mut self, interface: &'static Connection) -> bool {
+        (**self).init(interface)
+    }
+
+    fn get_info(&self) -> u16 {
+        (**self).get_info()
+    }
+
+    fn done(&mut self) {
+        (**self).done()
+    }
+
+    fn register_extension_as(&mut self) -> &[u16] {
+        (**self).register_extension_as()
+    }
+
+    fn get_n_props(&self) -> usize {
+        (**self).get_n_props()
+    }
+
+    fn find_prop(&self, name: &[u16]) -> Option<usize> {
+        (**self).find_prop(name)
+    }
+
+    fn get_prop_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        (**self).get_prop_name(num, alias)
+    }
+
+    fn get_prop_val(&self, num: usize) -> AddInWrapperResult<ParamValue> {
+        (**self).get_prop_val(num)
+    }
+
+    fn set_prop_val(
+        &mut self,
+        num: usize,
+        val: ParamValue,
+    ) -> AddInWrapperResult<()> {
+        (**self).set_prop_val(num, val)
+    }
+
+    fn is_prop_readable(&self, num: usize) -> bool {
+        (**self).is_prop_readable(num)
+    }
+
+    fn is_prop_writable(&self, num: usize) -> bool {
+        (**self).is_prop_writable(num)
+    }
+
+    fn get_n_methods(&self) -> usize {
+        (**self).get_n_methods()
+    }
+
+    fn find_method(&self, name: &[u16]) -> Option<usize> {
+        (**self).find_method(name)
+    }
+
+    fn get_method_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        (**self).get_method_name(num, alias)
+    }
+
+    fn get_n_params(&self, num: usize) -> usize {
+        (**self).get_n_params(num)
+    }
+
+    fn get_param_def_value(
+        &self,
+        method_num: usize,
+        param_num: usize,
+    ) -> Option<ParamValue> {
+        (**self).get_param_def_value(method_num, param_num)
+    }
+
+    fn has_ret_val(&self, method_num: usize) -> bool {
+        (**self).has_ret_val(method_num)
+    }
+
+    fn call_as_proc(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<()> {
+        (**self).call_as_proc(method_num, params)
+    }
+
+    fn call_as_func(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue> {
+        (**self).call_as_func(method_num, params)
+    }
+
+    fn set_locale(&mut self, loc: &[u16]) {
+        (**self).set_locale(loc)
+    }
+
+    fn set_user_interface_language_code(&mut self, lang: &[u16]) {
+        (**self).set_user_interface_language_code(lang)
+    }
+}
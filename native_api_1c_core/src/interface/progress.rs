@@ -0,0 +1,157 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use super::ConnectionHandle;
+
+/// Minimal time between updates of the status line by default
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Last text, shown in the status line
+struct Shown {
+    at: Instant,
+    current: u64,
+    /// percent, or number of done steps if total is unknown
+    percent: u64,
+}
+
+/// Progress of a long operation, shown in the status line of 1C as
+/// `Importing: 45% (4500/10000)`. Updates are throttled: the status line is
+/// changed only when the percent changes, and not more often than once per
+/// interval, except for reaching the total. Progress is updated through
+/// `&self`, so it can be shared with background workers, e.g. in `Arc`. The
+/// status line is reset on drop
+/// ```ignore
+/// let progress = Progress::new(self.connection.clone(), "Importing", 10000);
+/// for (index, row) in rows.iter().enumerate() {
+///     import(row);
+///     progress.set(index as u64 + 1);
+/// }
+/// ```
+pub struct Progress {
+    connection: ConnectionHandle,
+    title: String,
+    total: u64,
+    current: AtomicU64,
+    interval: Duration,
+    shown: Mutex<Option<Shown>>,
+}
+
+impl Progress {
+    /// Creates progress at 0, nothing is shown until the first update
+    /// # Arguments
+    /// * `connection` - handle of the AddIn connection, progress is not
+    ///   shown while the AddIn is not initialized
+    /// * `title` - text before the percent, e.g. `Importing`
+    /// * `total` - number of steps of the operation, if it is 0, only the
+    ///   number of done steps is shown
+    pub fn new(connection: ConnectionHandle, title: &str, total: u64) -> Self {
+        Self {
+            connection,
+            title: title.to_string(),
+            total,
+            current: AtomicU64::new(0),
+            interval: DEFAULT_INTERVAL,
+            shown: Mutex::new(None),
+        }
+    }
+
+    /// Sets minimal time between updates of the status line, 250 ms by
+    /// default
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns number of done steps
+    pub fn current(&self) -> u64 {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    /// Sets number of done steps and updates the status line. Shown progress
+    /// doesn't go back, the status line is not updated with a value less than
+    /// already shown
+    pub fn set(&self, current: u64) {
+        self.current.store(current, Ordering::Relaxed);
+        self.update(current);
+    }
+
+    /// Adds done steps and updates the status line, can be called by several
+    /// workers at once
+    pub fn inc(&self, steps: u64) {
+        let current = self.current.fetch_add(steps, Ordering::Relaxed) + steps;
+        self.update(current);
+    }
+
+    /// Returns text for the status line, e.g. `Importing: 45% (4500/10000)`
+    pub fn message(&self) -> String {
+        self.message_for(self.current())
+    }
+
+    fn percent(&self, current: u64) -> u64 {
+        match self.total {
+            0 => 0,
+            total => (current.min(total) as u128 * 100 / total as u128) as u64,
+        }
+    }
+
+    fn message_for(&self, current: u64) -> String {
+        match self.total {
+            0 => format!("{}: {}", self.title, current),
+            total => format!(
+                "{}: {}% ({}/{})",
+                self.title,
+                self.percent(current),
+                current,
+                total
+            ),
+        }
+    }
+
+    fn update(&self, current: u64) {
+        let Some(connection) = self.connection.get() else {
+            return;
+        };
+        // without total every step changes the text
+        let percent = match self.total {
+            0 => current,
+            _ => self.percent(current),
+        };
+        let mut shown =
+            self.shown.lock().unwrap_or_else(|err| err.into_inner());
+        let now = Instant::now();
+        // completion is shown at once, even if the interval has not passed
+        let done = self.total != 0 && current >= self.total;
+        if let Some(shown) = shown.as_ref() {
+            // workers may get here out of order, older values are dropped
+            if current < shown.current
+                || shown.percent == percent
+                || (!done && now.duration_since(shown.at) < self.interval)
+            {
+                return;
+            }
+        }
+        connection.set_status_line(&self.message_for(current));
+        *shown = Some(Shown {
+            at: now,
+            current,
+            percent,
+        });
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        let shown = self.shown.get_mut().unwrap_or_else(|err| err.into_inner());
+        if shown.is_none() {
+            return;
+        }
+        if let Some(connection) = self.connection.get() {
+            connection.reset_status_line();
+        }
+    }
+}
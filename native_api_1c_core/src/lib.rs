@@ -11,3 +11,11 @@
 pub mod ffi;
 /// Module for high level interface of Native API
 pub mod interface;
+
+/// `rust_decimal` crate, used for `Decimal` values
+#[cfg(feature = "decimal")]
+pub extern crate rust_decimal;
+
+/// `serde` crate, used for `Json` values
+#[cfg(feature = "serde")]
+pub extern crate serde;
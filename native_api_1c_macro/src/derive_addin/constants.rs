@@ -1,25 +1,55 @@
 pub const BOOL_TYPE: &str = "Bool";
 pub const I32_TYPE: &str = "Int";
+pub const I16_TYPE: &str = "Int16";
+pub const I64_TYPE: &str = "Int64";
+pub const U8_TYPE: &str = "UInt8";
+pub const U32_TYPE: &str = "UInt32";
+pub const U64_TYPE: &str = "UInt64";
+pub const USIZE_TYPE: &str = "USize";
 pub const F64_TYPE: &str = "Float";
 pub const STRING_TYPE: &str = "Str";
 pub const DATE_TYPE: &str = "Date";
 pub const BLOB_TYPE: &str = "Blob";
+pub const ANY_TYPE: &str = "Any";
+pub const DECIMAL_TYPE: &str = "Decimal";
+pub const JSON_TYPE: &str = "Json";
+pub const CUSTOM_TYPE: &str = "Custom";
 pub const UNTYPED_TYPE: &str = "None";
 
 pub const ALL_RETURN_TYPES: &[&str] = &[
     BOOL_TYPE,
     I32_TYPE,
+    I16_TYPE,
+    I64_TYPE,
+    U8_TYPE,
+    U32_TYPE,
+    U64_TYPE,
+    USIZE_TYPE,
     F64_TYPE,
+    DECIMAL_TYPE,
     STRING_TYPE,
     DATE_TYPE,
     BLOB_TYPE,
+    ANY_TYPE,
+    JSON_TYPE,
+    CUSTOM_TYPE,
     UNTYPED_TYPE,
 ];
 pub const ALL_ARG_TYPES: &[&str] = &[
     BOOL_TYPE,
     I32_TYPE,
+    I16_TYPE,
+    I64_TYPE,
+    U8_TYPE,
+    U32_TYPE,
+    U64_TYPE,
+    USIZE_TYPE,
     F64_TYPE,
+    DECIMAL_TYPE,
     STRING_TYPE,
     DATE_TYPE,
     BLOB_TYPE,
+    ANY_TYPE,
+    JSON_TYPE,
+    CUSTOM_TYPE,
 ];
@@ -0,0 +1,166 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, DataStruct};
+
+use super::{
+    names::{is_valid_name, NAME_RULES},
+    utils::macros::tkn_err,
+};
+
+/// Field with nested AddIn, marked with `#[add_in_flatten]`
+pub struct FlattenDesc {
+    pub member: syn::Member,
+    pub ty: syn::Type,
+    pub prefix: String,
+    pub prefix_ru: String,
+}
+
+/// Options of the nested AddIn, set with `#[add_in_flatten(...)]`
+/// # Fields
+/// * `prefix` - prefix of English names of nested members, empty by default
+/// * `prefix_ru` - prefix of Russian names of nested members, must be set
+///   together with `prefix`, so Russian names don't get English prefix
+#[derive(FromMeta, Debug, Default)]
+struct FlattenMeta {
+    prefix: Option<syn::LitStr>,
+    prefix_ru: Option<syn::LitStr>,
+}
+
+pub fn parse_flatten(struct_data: &DataStruct) -> Result<Vec<FlattenDesc>, darling::Error> {
+    let mut flattened = vec![];
+    for (index, field) in struct_data.fields.iter().enumerate() {
+        let attrs: Vec<_> = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("add_in_flatten"))
+            .collect();
+        let attr = match attrs.as_slice() {
+            [] => continue,
+            [attr] => *attr,
+            [_, attr, ..] => {
+                return tkn_err!("Field can have only 1 `add_in_flatten` attribute", attr)
+            }
+        };
+        let other_attr = field.attrs.iter().find(|attr| {
+            ["add_in_prop", "add_in_func", "add_in_con"]
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+        if let Some(other_attr) = other_attr {
+            return tkn_err!(
+                "`add_in_flatten` field can't be property, function or connection",
+                other_attr
+            );
+        }
+
+        let flatten_meta = match &attr.meta {
+            syn::Meta::Path(_) => FlattenMeta::default(),
+            meta => FlattenMeta::from_meta(meta)?,
+        };
+        for prefix in [&flatten_meta.prefix, &flatten_meta.prefix_ru]
+            .into_iter()
+            .flatten()
+        {
+            if !is_valid_name(&prefix.value()) {
+                return tkn_err!(
+                    format!("invalid prefix `{}`: {NAME_RULES}", prefix.value()),
+                    prefix
+                );
+            }
+        }
+        let (prefix, prefix_ru) = match (flatten_meta.prefix, flatten_meta.prefix_ru) {
+            (Some(prefix), Some(prefix_ru)) => (prefix.value(), prefix_ru.value()),
+            (None, None) => (String::new(), String::new()),
+            (Some(prefix), None) | (None, Some(prefix)) => {
+                return tkn_err!(
+                    "`prefix` and `prefix_ru` must be set together, \
+                    e.g. `prefix = \"Log\", prefix_ru = \"Журнал\"`",
+                    &prefix
+                );
+            }
+        };
+
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+        flattened.push(FlattenDesc {
+            member,
+            ty: field.ty.clone(),
+            prefix,
+            prefix_ru,
+        });
+    }
+    Ok(flattened)
+}
+
+/// Assertions, that members of nested AddIns don't reuse names of own members and of
+/// members of other nested AddIns, when prefixes are added. Nested tables are known only
+/// by the type of the field, so names are compared in const context
+pub fn flatten_names_check_tkn(
+    self_ty: &TokenStream,
+    flattened: &[FlattenDesc],
+) -> Vec<TokenStream> {
+    let descriptors = quote! { native_api_1c::native_api_1c_core::interface::descriptors };
+    let mut assertions = vec![];
+    for (index, flatten) in flattened.iter().enumerate() {
+        let FlattenDesc {
+            member,
+            ty,
+            prefix,
+            prefix_ru,
+        } = flatten;
+        let member_str = member.to_token_stream().to_string();
+        let message = format!("members of flattened `{member_str}` reuse names of own members");
+        assertions.push(quote_spanned! { ty.span() =>
+            assert!(
+                !#descriptors::members_overlap::<#self_ty, #ty>(["", ""], [#prefix, #prefix_ru]),
+                #message,
+            )
+        });
+        for other in &flattened[..index] {
+            let other_ty = &other.ty;
+            let (other_prefix, other_prefix_ru) = (&other.prefix, &other.prefix_ru);
+            let message = format!(
+                "members of flattened `{}` and `{member_str}` have the same names",
+                other.member.to_token_stream()
+            );
+            assertions.push(quote_spanned! { ty.span() =>
+                assert!(
+                    !#descriptors::members_overlap::<#other_ty, #ty>(
+                        [#other_prefix, #other_prefix_ru],
+                        [#prefix, #prefix_ru],
+                    ),
+                    #message,
+                )
+            });
+        }
+    }
+    assertions
+}
+
+pub fn flatten_table_tkn(flattened: &[FlattenDesc]) -> TokenStream {
+    let descriptors = flattened.iter().map(|flatten| {
+        let FlattenDesc {
+            member,
+            ty,
+            prefix,
+            prefix_ru,
+        } = flatten;
+        // spanned to the field type, so it is reported if the type is not an AddIn
+        let get = quote_spanned! { ty.span() => |addin| &addin.#member };
+        let get_mut = quote_spanned! { ty.span() => |addin| &mut addin.#member };
+        quote! {
+            native_api_1c::native_api_1c_core::interface::descriptors::FlattenDescriptor {
+                prefix: #prefix,
+                prefix_ru: #prefix_ru,
+                get: #get,
+                get_mut: #get_mut,
+            }
+        }
+    });
+    quote! {
+        &[#(#descriptors),*]
+    }
+}
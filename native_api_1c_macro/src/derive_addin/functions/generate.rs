@@ -2,9 +2,72 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::Ident;
 
-use crate::derive_addin::utils::{expr_from_os_value, expr_to_os_value};
+use crate::derive_addin::utils::{
+    expr_from_os_value, expr_to_os_value, optional_expr_from_os_value, try_expr_to_os_value,
+};
 
-use super::{FuncArgumentDesc, FuncDesc, FuncParamType};
+use super::{FuncArgumentDesc, FuncDesc, FuncKind, FuncParamType};
+
+pub fn method_table_tkn(functions: &[FuncDesc]) -> TokenStream {
+    let descriptors = functions.iter().map(method_descriptor_tkn);
+    quote! {
+        &[#(#descriptors),*]
+    }
+}
+
+fn method_descriptor_tkn(func: &FuncDesc) -> TokenStream {
+    let name = &func.name;
+    let name_ru = &func.name_ru;
+
+    let params = func.get_1c_params().into_iter().map(|param| {
+        let default = match (&param.default, &param.ty) {
+            (Some(expr), FuncParamType::PlatformType(ty)) => {
+                let default_value = expr_to_os_value(expr, ty, true);
+                quote! { Some(|| #default_value) }
+            }
+            // optional parameter can be omitted in 1C, it is passed as `Undefined`
+            (None, _) if param.optional => quote! {
+                Some(|| native_api_1c::native_api_1c_core::interface::ParamValue::Empty)
+            },
+            _ => quote! { None },
+        };
+        quote! {
+            native_api_1c::native_api_1c_core::interface::descriptors::ParamDescriptor {
+                default: #default,
+            }
+        }
+    });
+
+    let has_ret_val = func.return_value.ty.is_some();
+    let call = if has_ret_val {
+        let return_val_ident = Ident::new("val", Span::call_site());
+        let call_func = func_call_tkn(func, Some(&return_val_ident));
+        quote! {
+            |addin, params| {
+                #call_func
+                Ok(val)
+            }
+        }
+    } else {
+        let call_func = func_call_tkn(func, None);
+        quote! {
+            |addin, params| {
+                #call_func
+                Ok(native_api_1c::native_api_1c_core::interface::ParamValue::Empty)
+            }
+        }
+    };
+
+    quote! {
+        native_api_1c::native_api_1c_core::interface::descriptors::MethodDescriptor {
+            name: #name,
+            name_ru: #name_ru,
+            params: &[#(#params),*],
+            has_ret_val: #has_ret_val,
+            call: #call,
+        }
+    }
+}
 
 pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
     let func_ident = func.ident.clone();
@@ -31,20 +94,25 @@ pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
 
     if func.has_self_param() {
         if func_args.is_empty() {
-            func_args = quote! {self};
+            func_args = quote! {addin};
         } else {
-            func_args = quote! {self, #func_args};
+            func_args = quote! {addin, #func_args};
         }
     }
 
-    let mut func_call = quote! {
-        let call_result = (self.#func_ident)(#func_args);
+    let mut func_call = match func.kind {
+        FuncKind::Field => quote! {
+            let call_result = (addin.#func_ident)(#func_args);
+        },
+        FuncKind::Method => quote! {
+            let call_result = Self::#func_ident(#func_args);
+        },
     };
 
     if func.return_value.result {
         func_call.extend(quote! {
             if call_result.is_err() {
-                return Err(());
+                return Err(native_api_1c::native_api_1c_core::interface::descriptors::MethodError::Failed);
             }
             let call_result = call_result.unwrap();
         });
@@ -52,12 +120,21 @@ pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
 
     if let Some(set_to) = set_to {
         let return_ty = func.return_value.ty.clone().unwrap();
-        let result_wrap = expr_to_os_value(&quote! { call_result }, &return_ty, true);
+        let result_wrap = try_expr_to_os_value(
+            &quote! { call_result },
+            &return_ty,
+            true,
+            func.return_value.optional,
+        );
         func_call.extend(quote! {
             let #set_to
         });
         func_call.extend(quote! { = });
-        func_call.extend(quote! {#result_wrap;});
+        func_call.extend(quote! {
+            #result_wrap.map_err(
+                native_api_1c::native_api_1c_core::interface::descriptors::MethodError::ReturnValue
+            )?;
+        });
     }
 
     quote! {
@@ -76,10 +153,54 @@ fn gen_param_prep(
         panic!("SelfType is not allowed here");
     };
 
-    let param_unwrap = expr_from_os_value(&quote! { params[#param_index]}, param_ty);
-    let mut pre_call = quote! {;
-        let #param_ident = #param_unwrap;
-        let mut #param_ident = #param_ident.clone().into();
+    // conversion error is returned with the argument number and name, to be reported to 1C
+    let param_name = match &param.name {
+        Some(name) => quote! { Some(#name) },
+        None => quote! { None },
+    };
+    let map_err = quote! {
+        .map_err(|err| {
+            native_api_1c::native_api_1c_core::interface::descriptors::MethodError::argument(
+                #param_index,
+                #param_name,
+                err,
+            )
+        })?
+    };
+
+    // missing values are filled with defaults before the call, descriptor called directly
+    // gets an error instead of a panic
+    let missing_err = quote! {
+        native_api_1c::native_api_1c_core::interface::descriptors::MethodError::MissingArgument {
+            index: #param_index,
+        }
+    };
+    let param_value = quote! { (*#param_ident) };
+    let get_param = quote! {;
+        let #param_ident = params.get(#param_index).ok_or(#missing_err)?;
+    };
+
+    let mut pre_call = if param_ty.uses_traits() {
+        // custom type is inferred from the function signature, so no `.into()`
+        let param_unwrap = expr_from_os_value(&param_value, param_ty, &param.coercion);
+        quote! {
+            #get_param
+            let mut #param_ident = #param_unwrap #map_err;
+        }
+    } else if param.optional {
+        let param_unwrap = optional_expr_from_os_value(&param_value, param_ty, &param.coercion);
+        quote! {
+            #get_param
+            let #param_ident = #param_unwrap #map_err;
+            let mut #param_ident = #param_ident.map(Into::into);
+        }
+    } else {
+        let param_unwrap = expr_from_os_value(&param_value, param_ty, &param.coercion);
+        quote! {
+            #get_param
+            let #param_ident = #param_unwrap #map_err;
+            let mut #param_ident = #param_ident.clone().into();
+        }
     };
     if param.out_param {
         pre_call.extend(quote! {
@@ -90,13 +211,15 @@ fn gen_param_prep(
     let post_call = if !param.out_param {
         quote! {}
     } else {
-        let param_wrap = expr_to_os_value(&param_ident.to_token_stream(), param_ty, false);
-        let mut q = quote! {
-            params[#param_index]
-        };
-        q.extend(quote! { = });
-        q.extend(quote! { #param_wrap; });
-        q
+        let param_wrap = try_expr_to_os_value(
+            &param_ident.to_token_stream(),
+            param_ty,
+            false,
+            param.optional,
+        );
+        quote! {
+            *params.get_mut(#param_index).ok_or(#missing_err)? = #param_wrap #map_err;
+        }
     };
 
     (pre_call, post_call)
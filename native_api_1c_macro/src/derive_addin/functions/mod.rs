@@ -2,28 +2,40 @@ use std::fmt::Display;
 
 use darling::FromMeta;
 use proc_macro2::{Ident, TokenStream};
-use quote::{quote, ToTokens};
+use quote::ToTokens;
 
 use super::{
-    constants::{BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, I32_TYPE, STRING_TYPE},
-    parsers::ParamType,
+    constants::{
+        ANY_TYPE, BLOB_TYPE, BOOL_TYPE, CUSTOM_TYPE, DATE_TYPE, DECIMAL_TYPE, F64_TYPE, I16_TYPE,
+        I32_TYPE, I64_TYPE, JSON_TYPE, STRING_TYPE, U32_TYPE, U64_TYPE, U8_TYPE, USIZE_TYPE,
+    },
+    parsers::{CoercionDesc, ParamType, PropName},
 };
 
-pub mod collectors;
 pub mod generate;
 pub mod parse;
 
 #[derive(Debug)]
 pub struct FuncDesc {
     pub ident: Ident,
+    pub kind: FuncKind,
 
-    pub name_literal: TokenStream,
-    pub name_ru_literal: TokenStream,
+    pub name: PropName,
+    pub name_ru: PropName,
 
     pub params: Vec<FuncArgumentDesc>,
     pub return_value: ReturnTypeDesc,
 }
 
+/// How the function is declared and called
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FuncKind {
+    /// `fn` pointer field of the struct, called as `(addin.field)(...)`
+    Field,
+    /// Method of the impl block with `#[add_in_methods]`, called as `Self::method(...)`
+    Method,
+}
+
 impl FuncDesc {
     pub fn get_1c_params(&self) -> Vec<&FuncArgumentDesc> {
         self.params
@@ -42,14 +54,19 @@ impl FuncDesc {
 #[derive(Debug)]
 pub struct FuncArgumentDesc {
     pub ty: FuncParamType,
+    /// Name of the argument, used in conversion error messages
+    pub name: Option<syn::LitStr>,
     pub default: Option<TokenStream>,
     pub out_param: bool,
+    pub optional: bool,
+    pub coercion: CoercionDesc,
 }
 
 #[derive(Debug)]
 pub struct ReturnTypeDesc {
     pub ty: Option<ParamType>,
     pub result: bool,
+    pub optional: bool,
 }
 const META_TYPE_ERR: &str = "expected string literal or path";
 
@@ -85,11 +102,15 @@ impl FromMeta for FuncParamType {
 
     fn from_string(value: &str) -> darling::Result<Self> {
         let joined_allowed_types = crate::derive_addin::constants::ALL_ARG_TYPES.join(", ");
-        Self::try_from(value).map_err(|_| {
-            darling::Error::custom(format!(
+        match Self::try_from(value) {
+            Ok(FuncParamType::PlatformType(ty)) => {
+                Ok(FuncParamType::PlatformType(ty.check_enabled()?))
+            }
+            Ok(ty) => Ok(ty),
+            Err(_) => Err(darling::Error::custom(format!(
                 "unknown type `{value}`. Must be one of: {joined_allowed_types}",
-            ))
-        })
+            ))),
+        }
     }
 }
 
@@ -100,10 +121,20 @@ impl TryFrom<&str> for FuncParamType {
         match value {
             BOOL_TYPE => Ok(FuncParamType::PlatformType(ParamType::Bool)),
             I32_TYPE => Ok(FuncParamType::PlatformType(ParamType::I32)),
+            I16_TYPE => Ok(FuncParamType::PlatformType(ParamType::I16)),
+            I64_TYPE => Ok(FuncParamType::PlatformType(ParamType::I64)),
+            U8_TYPE => Ok(FuncParamType::PlatformType(ParamType::U8)),
+            U32_TYPE => Ok(FuncParamType::PlatformType(ParamType::U32)),
+            U64_TYPE => Ok(FuncParamType::PlatformType(ParamType::U64)),
+            USIZE_TYPE => Ok(FuncParamType::PlatformType(ParamType::Usize)),
             F64_TYPE => Ok(FuncParamType::PlatformType(ParamType::F64)),
+            DECIMAL_TYPE => Ok(FuncParamType::PlatformType(ParamType::Decimal)),
             STRING_TYPE => Ok(FuncParamType::PlatformType(ParamType::String)),
             DATE_TYPE => Ok(FuncParamType::PlatformType(ParamType::Date)),
             BLOB_TYPE => Ok(FuncParamType::PlatformType(ParamType::Blob)),
+            ANY_TYPE => Ok(FuncParamType::PlatformType(ParamType::Any)),
+            JSON_TYPE => Ok(FuncParamType::PlatformType(ParamType::Json)),
+            CUSTOM_TYPE => Ok(FuncParamType::PlatformType(ParamType::Custom)),
             _ => Err(()),
         }
     }
@@ -113,26 +144,7 @@ impl ToTokens for FuncParamType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         *tokens = match self {
             FuncParamType::SelfType => panic!("type not supported for selection"),
-            FuncParamType::PlatformType(param_type) => match param_type {
-                ParamType::Bool => {
-                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Bool }
-                }
-                ParamType::I32 => {
-                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::I32 }
-                }
-                ParamType::F64 => {
-                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::F64 }
-                }
-                ParamType::String => {
-                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::String }
-                }
-                ParamType::Date => {
-                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Date }
-                }
-                ParamType::Blob => {
-                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Blob }
-                }
-            },
+            FuncParamType::PlatformType(param_type) => param_type.to_token_stream(),
         }
     }
 }
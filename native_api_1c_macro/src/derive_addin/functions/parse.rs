@@ -1,52 +1,147 @@
 use darling::{FromField, FromMeta};
 use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
-use syn::{spanned::Spanned, Attribute, DataStruct, Meta};
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Attribute, DataStruct, Ident, Meta};
 
 use crate::derive_addin::{
-    parsers::{ParamType, PropName},
-    utils::ident_option_to_darling_err,
+    parsers::{CoercionDesc, ParamType, PropName},
+    utils::{ident_option_to_darling_err, option_inner_type},
 };
 
-use super::{FuncArgumentDesc, FuncDesc, FuncParamType, ReturnTypeDesc};
+use super::{FuncArgumentDesc, FuncDesc, FuncKind, FuncParamType, ReturnTypeDesc};
 
 impl FromField for FuncDesc {
     fn from_field(field: &syn::Field) -> darling::Result<Self> {
         let field_ident = ident_option_to_darling_err(field.ident.as_ref())?;
 
-        let add_in_func_attr: Vec<&Attribute> = field
-            .attrs
+        let syn::Type::BareFn(bare_fn) = &field.ty else {
+            return Err(
+                darling::Error::custom("AddIn functions must have bare `fn` type")
+                    .with_span(field_ident),
+            );
+        };
+
+        let mut self_param = None;
+        if let Some(first_input) = bare_fn.inputs.first() {
+            let arg_tkn_stream: TokenStream = first_input.to_token_stream();
+
+            if let Ok(reference) = syn::parse2::<syn::TypeReference>(arg_tkn_stream.clone()) {
+                if arg_tkn_stream
+                    .into_iter()
+                    .filter(|t| t.to_string() == "Self")
+                    .count()
+                    == 1
+                {
+                    self_param = Some(reference.mutability.is_some());
+                };
+            };
+        };
+
+        let inputs: Vec<&syn::Type> = bare_fn
+            .inputs
+            .iter()
+            .skip(usize::from(self_param.is_some()))
+            .map(|input| &input.ty)
+            .collect();
+
+        Self::from_signature(
+            field_ident,
+            FuncKind::Field,
+            &field.attrs,
+            self_param,
+            &inputs,
+            &bare_fn.output,
+        )
+    }
+}
+
+impl FuncDesc {
+    /// Parses method of the impl block with `#[add_in_methods]`
+    pub fn from_impl_fn(item_fn: &syn::ImplItemFn) -> darling::Result<Self> {
+        let sig = &item_fn.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(
+                darling::Error::custom("AddIn methods can't have generic parameters")
+                    .with_span(&sig.generics),
+            );
+        }
+        if let Some(asyncness) = &sig.asyncness {
+            return Err(
+                darling::Error::custom("AddIn methods can't be `async`").with_span(asyncness)
+            );
+        }
+
+        let mut self_param = None;
+        let mut inputs = vec![];
+        for input in &sig.inputs {
+            match input {
+                syn::FnArg::Receiver(receiver) => match receiver.ty.as_ref() {
+                    syn::Type::Reference(reference) => {
+                        self_param = Some(reference.mutability.is_some());
+                    }
+                    _ => {
+                        return Err(darling::Error::custom(
+                            "AddIn methods can take `self` only as `&self` or `&mut self`",
+                        )
+                        .with_span(receiver))
+                    }
+                },
+                syn::FnArg::Typed(pat_type) => inputs.push(pat_type.ty.as_ref()),
+            }
+        }
+
+        Self::from_signature(
+            &sig.ident,
+            FuncKind::Method,
+            &item_fn.attrs,
+            self_param,
+            &inputs,
+            &sig.output,
+        )
+    }
+
+    /// Parses function from its attributes and signature
+    /// # Arguments
+    /// * `self_param` - `Some(mutable)` if function takes `&Self` or `&mut Self` first
+    /// * `inputs` - types of other arguments
+    fn from_signature(
+        ident: &Ident,
+        kind: FuncKind,
+        attrs: &[Attribute],
+        self_param: Option<bool>,
+        inputs: &[&syn::Type],
+        output: &syn::ReturnType,
+    ) -> darling::Result<Self> {
+        let add_in_func_attr: Vec<&Attribute> = attrs
             .iter()
             .filter(|attr| attr.path().is_ident("add_in_func"))
             .collect();
         if add_in_func_attr.is_empty() {
             return Err(
-                darling::Error::custom("Field must have `add_in_func` attribute")
-                    .with_span(field_ident),
+                darling::Error::custom("Function must have `add_in_func` attribute")
+                    .with_span(ident),
             );
         } else if add_in_func_attr.len() > 1 {
             return Err(
-                darling::Error::custom("Field can have only 1 `add_in_func` attribute")
-                    .with_span(field_ident),
+                darling::Error::custom("Function can have only 1 `add_in_func` attribute")
+                    .with_span(ident),
             );
         };
         let add_in_func_attr = add_in_func_attr[0];
 
-        let arg_attrs: Vec<&Attribute> = field
-            .attrs
+        let arg_attrs: Vec<&Attribute> = attrs
             .iter()
             .filter(|attr| attr.path().is_ident("arg"))
             .collect();
 
-        let returns_attrs: Vec<&Attribute> = field
-            .attrs
+        let returns_attrs: Vec<&Attribute> = attrs
             .iter()
             .filter(|attr| attr.path().is_ident("returns"))
             .collect();
         if returns_attrs.len() > 1 {
             return Err(
-                darling::Error::custom("Field can have at most 1 `returns` attribute")
-                    .with_span(field_ident),
+                darling::Error::custom("Function can have at most 1 `returns` attribute")
+                    .with_span(ident),
             );
         };
         let returns_attr = returns_attrs.first().copied();
@@ -56,57 +151,41 @@ impl FromField for FuncDesc {
             .iter()
             .map(|attr| FuncArgumentMeta::from_meta(&attr.meta))
             .collect::<darling::Result<Vec<FuncArgumentMeta>>>()?;
-        let return_meta = returns_attr
-            .map(|attr| FuncReturnMeta::from_meta(&attr.meta))
-            .transpose()?;
-        let return_value = match return_meta {
-            Some(meta) => ReturnTypeDesc::from(meta),
-            None => ReturnTypeDesc {
-                ty: None,
-                result: false,
-            },
-        };
 
-        let mut params = params_meta
-            .into_iter()
-            .map(FuncArgumentDesc::try_from)
-            .map(|res| res.map_err(|err| err.into()))
-            .collect::<Result<Vec<FuncArgumentDesc>, darling::Error>>()?;
+        let mut params = vec![];
+        if let Some(mutable) = self_param {
+            params.push(FuncArgumentDesc {
+                ty: FuncParamType::SelfType,
+                name: None,
+                default: None,
+                out_param: mutable,
+                optional: false,
+                coercion: CoercionDesc::default(),
+            });
+        }
 
-        let syn::Type::BareFn(bare_fn) = &field.ty else {
-            return Err(
-                darling::Error::custom("AddIn functions must have bare `fn` type")
-                    .with_span(field_ident),
-            );
-        };
+        // `#[arg(...)]` attributes are matched with arguments in the signature by position,
+        // arguments without attribute get type inferred from the signature
+        if let Some(extra_attr) = arg_attrs.get(inputs.len()) {
+            return Err(darling::Error::custom(
+                "`arg` attribute has no matching argument in the function signature",
+            )
+            .with_span(&extra_attr.meta));
+        }
+        let mut params_meta = params_meta.into_iter();
+        for input in inputs {
+            let arg_meta = params_meta.next().unwrap_or_default();
+            params.push(FuncArgumentDesc::from_signature(arg_meta, input)?);
+        }
 
-        if let Some(first_input) = bare_fn.inputs.first() {
-            let arg_tkn_stream: TokenStream = first_input.to_token_stream();
-
-            if let Ok(reference) = syn::parse2::<syn::TypeReference>(arg_tkn_stream.clone()) {
-                if arg_tkn_stream
-                    .into_iter()
-                    .filter(|t| t.to_string() == "Self")
-                    .count()
-                    == 1
-                {
-                    params.insert(
-                        0,
-                        FuncArgumentDesc {
-                            ty: FuncParamType::SelfType,
-                            default: None,
-                            out_param: reference.mutability.is_some(),
-                        },
-                    )
-                };
-            };
-        };
+        let return_value = ReturnTypeDesc::from_signature(returns_attr, output)?;
 
         Ok(Self {
-            ident: field_ident.to_owned(),
+            ident: ident.to_owned(),
+            kind,
 
-            name_literal: func_meta.name.into(),
-            name_ru_literal: func_meta.name_ru.into(),
+            name: func_meta.name,
+            name_ru: func_meta.name_ru,
 
             params,
             return_value,
@@ -120,58 +199,132 @@ struct FuncHeadMeta {
     name_ru: PropName,
 }
 
-#[derive(FromMeta, Debug)]
+#[derive(FromMeta, Debug, Default)]
 struct FuncArgumentMeta {
     ident: Option<syn::Ident>,
-    ty: FuncParamType,
+    ty: Option<FuncParamType>,
+    name: Option<syn::LitStr>,
     default: Option<Meta>,
     #[allow(dead_code)]
     as_in: Option<()>,
     as_out: Option<()>,
+    optional: Option<()>,
+    strict: Option<()>,
+    coerce_bool: Option<()>,
+    coerce_string: Option<()>,
 }
 
-impl TryFrom<FuncArgumentMeta> for FuncArgumentDesc {
-    type Error = ErrorConvertingMeta;
-
-    fn try_from(arg_meta: FuncArgumentMeta) -> Result<Self, Self::Error> {
+impl FuncArgumentDesc {
+    /// Builds argument from `#[arg(...)]` attribute, or default one if it is omitted,
+    /// and the type of the argument in the function signature
+    fn from_signature(arg_meta: FuncArgumentMeta, sig_ty: &syn::Type) -> darling::Result<Self> {
         if arg_meta.as_in.is_some() && arg_meta.as_out.is_some() {
-            return Err(Self::Error::ConflictingParams(
+            return Err(ErrorConvertingMeta::ConflictingParams(
                 arg_meta.ident.span(),
                 "as_in".to_string(),
                 "as_out".to_string(),
-            ));
+            )
+            .into());
         }
 
-        let allowed_defaults = match arg_meta.ty.clone() {
-            FuncParamType::SelfType => false,
-            FuncParamType::PlatformType(ty) => match ty {
-                ParamType::Bool => true,
-                ParamType::I32 => true,
-                ParamType::F64 => true,
-                ParamType::String => true,
-                ParamType::Date => false,
-                ParamType::Blob => false,
-            },
+        if let Some(name) = arg_meta
+            .name
+            .as_ref()
+            .filter(|name| name.value().is_empty())
+        {
+            return Err(darling::Error::custom("argument name can't be empty").with_span(name));
+        }
+
+        let is_mut_ref =
+            matches!(sig_ty, syn::Type::Reference(reference) if reference.mutability.is_some());
+        if arg_meta.as_out.is_some() && !is_mut_ref {
+            return Err(darling::Error::custom(
+                "`as_out` argument must be `&mut` in the function signature",
+            )
+            .with_span(sig_ty));
+        }
+        if arg_meta.as_in.is_some() && is_mut_ref {
+            return Err(darling::Error::custom(
+                "`as_in` argument can't be `&mut` in the function signature",
+            )
+            .with_span(sig_ty));
+        }
+
+        let coercion = CoercionDesc::from_flags(
+            arg_meta.strict,
+            arg_meta.coerce_bool,
+            arg_meta.coerce_string,
+        )
+        .map_err(|(param1, param2)| {
+            ErrorConvertingMeta::ConflictingParams(
+                arg_meta.ident.span(),
+                param1.to_string(),
+                param2.to_string(),
+            )
+        })?;
+
+        let attr_ty = match arg_meta.ty {
+            Some(FuncParamType::PlatformType(ty)) => Some(ty),
+            _ => None,
+        };
+        let ty = resolve_type(attr_ty, sig_ty, "arg")?;
+        if let Some(flag) = coercion.unsupported_flag(&ty) {
+            return Err(darling::Error::custom(format!(
+                "`{flag}` has no effect for `{}` type",
+                ty.name()
+            ))
+            .with_span(sig_ty));
+        }
+
+        let allowed_defaults = match ty {
+            ParamType::Bool => true,
+            ParamType::I32 => true,
+            ParamType::I16 => true,
+            ParamType::I64 => true,
+            ParamType::U8 => true,
+            ParamType::U32 => true,
+            ParamType::U64 => true,
+            ParamType::Usize => true,
+            ParamType::F64 => true,
+            ParamType::String => true,
+            ParamType::Date => true,
+            ParamType::Blob => true,
+            ParamType::Decimal => false,
+            ParamType::Any => false,
+            ParamType::Json => false,
+            ParamType::Custom => true,
         };
 
         if arg_meta.default.is_some() && !allowed_defaults {
-            return Err(Self::Error::TypeCannotBeDefault(
-                arg_meta.ty,
+            return Err(ErrorConvertingMeta::TypeCannotBeDefault(
+                FuncParamType::PlatformType(ty),
                 arg_meta.default.span(),
-            ));
+            )
+            .into());
         }
 
         // if you pass "some_string" as default, it would get parsed by darling as `Ident`
         let default_fixed = arg_meta.default.map(|d| match d {
-            Meta::NameValue(nv) => Ok(nv.value.to_token_stream()),
-            _ => Err(Self::Error::UnexpectedMetaType(arg_meta.ident.span())),
+            Meta::NameValue(nv) => match ty {
+                ParamType::Date => date_default_tkn(&nv.value),
+                ParamType::Blob => blob_default_tkn(&nv.value),
+                _ => Ok(nv.value.to_token_stream()),
+            },
+            _ => Err(ErrorConvertingMeta::UnexpectedMetaType(arg_meta.ident.span()).into()),
         });
         let default_fixed = default_fixed.transpose()?;
 
+        // `Option<T>` in the signature makes argument optional,
+        // even without `optional` in the attribute
+        let optional = arg_meta.optional.is_some() || option_inner_type(sig_ty).is_some();
+
         Ok(Self {
-            ty: arg_meta.ty,
+            ty: FuncParamType::PlatformType(ty),
+            name: arg_meta.name,
             default: default_fixed,
-            out_param: arg_meta.as_out.is_some(),
+            out_param: is_mut_ref,
+            optional,
+            coercion,
         })
     }
 }
@@ -180,14 +333,219 @@ impl TryFrom<FuncArgumentMeta> for FuncArgumentDesc {
 struct FuncReturnMeta {
     ty: Option<ParamType>,
     result: Option<()>,
+    optional: Option<()>,
+}
+
+impl ReturnTypeDesc {
+    /// Builds return value from `#[returns(...)]` attribute, if it is set, and the return
+    /// type in the function signature
+    fn from_signature(
+        returns_attr: Option<&Attribute>,
+        output: &syn::ReturnType,
+    ) -> darling::Result<Self> {
+        let return_meta = returns_attr
+            .map(|attr| FuncReturnMeta::from_meta(&attr.meta))
+            .transpose()?;
+
+        let output_ty = match output {
+            syn::ReturnType::Default => None,
+            syn::ReturnType::Type(_, ty) => Some(ty.as_ref()),
+        };
+        let ok_ty = output_ty.and_then(result_ok_type);
+        let result = ok_ty.is_some();
+        let value_ty = ok_ty.or(output_ty).filter(|ty| !is_unit_type(ty));
+
+        let (attr_ty, optional) = match return_meta {
+            Some(meta) => {
+                let span = output_ty.map_or_else(|| returns_attr.span(), |ty| ty.span());
+                if meta.result.is_some() && !result {
+                    return Err(darling::Error::custom(
+                        "`result` is set, but function does not return `Result`",
+                    )
+                    .with_span(&span));
+                }
+                if meta.result.is_none() && result {
+                    return Err(darling::Error::custom(
+                        "function returns `Result`, `result` must be set in `returns` attribute",
+                    )
+                    .with_span(&span));
+                }
+                (meta.ty.map(|ty| (ty, span)), meta.optional.is_some())
+            }
+            None => (None, false),
+        };
+
+        let ty = match (attr_ty, value_ty) {
+            (None, None) => None,
+            (Some((ty, span)), None) => {
+                return Err(darling::Error::custom(format!(
+                    "`ty = {}` is set, but function returns nothing",
+                    ty.name()
+                ))
+                .with_span(&span));
+            }
+            (attr_ty, Some(value_ty)) => Some(resolve_type(
+                attr_ty.map(|(ty, _)| ty),
+                value_ty,
+                "returns",
+            )?),
+        };
+
+        // `Option<T>` in the signature makes return value optional,
+        // even without `optional` in the attribute
+        let optional = optional || value_ty.and_then(option_inner_type).is_some();
+
+        Ok(Self {
+            ty,
+            result,
+            optional,
+        })
+    }
 }
 
-impl From<FuncReturnMeta> for ReturnTypeDesc {
-    fn from(arg_meta: FuncReturnMeta) -> Self {
-        Self {
-            ty: arg_meta.ty,
-            result: arg_meta.result.is_some(),
+/// Returns type, set in attribute, or inferred from the type in the signature.
+/// If both are known, they must match, except for `Custom`, that can be any type
+fn resolve_type(
+    attr_ty: Option<ParamType>,
+    sig_ty: &syn::Type,
+    attr_name: &str,
+) -> darling::Result<ParamType> {
+    match (attr_ty, ParamType::infer(sig_ty)) {
+        (Some(ty), Some(inferred)) if ty != inferred && ty != ParamType::Custom => {
+            Err(darling::Error::custom(format!(
+                "type in the function signature does not match `ty = {}`, expected `ty = {}`",
+                ty.name(),
+                inferred.name()
+            ))
+            .with_span(sig_ty))
         }
+        (Some(ty), _) => Ok(ty),
+        (None, Some(inferred)) => Ok(inferred.check_enabled()?),
+        (None, None) => Err(darling::Error::custom(format!(
+            "can't infer type from the function signature, set it with `#[{attr_name}(ty = ...)]`"
+        ))
+        .with_span(sig_ty)),
+    }
+}
+
+/// Parses date default, e.g. `"2024-01-31T12:00:00"` or `"2024-01-31"`, at compile time
+/// into `Tm` expression. Empty string is the empty date of 1C, `0001-01-01T00:00:00`
+fn date_default_tkn(expr: &syn::Expr) -> darling::Result<TokenStream> {
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(lit),
+        ..
+    }) = expr
+    else {
+        return Err(darling::Error::custom(
+            "`Date` default must be a string literal, e.g. `\"2024-01-31T12:00:00\"`",
+        )
+        .with_span(expr));
+    };
+    let value = lit.value();
+    let Some([year, month, day, hour, min, sec]) = parse_date(&value) else {
+        return Err(darling::Error::custom(format!(
+            "invalid date `{value}`, expected `YYYY-MM-DDThh:mm:ss`, `YYYY-MM-DD` or empty string"
+        ))
+        .with_span(lit));
+    };
+
+    // day of the week from Sunday, by Sakamoto's method
+    const MONTH_OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month < 3 { year - 1 } else { year };
+    let wday = (y + y / 4 - y / 100 + y / 400 + MONTH_OFFSETS[month as usize - 1] + day) % 7;
+    let yday = (1..month).map(|m| days_in_month(year, m)).sum::<i32>() + day - 1;
+    let (mon, year) = (month - 1, year - 1900);
+
+    Ok(quote! {
+        (native_api_1c::native_api_1c_core::ffi::provided_types::Tm {
+            sec: #sec,
+            min: #min,
+            hour: #hour,
+            mday: #day,
+            mon: #mon,
+            year: #year,
+            wday: #wday,
+            yday: #yday,
+            ..Default::default()
+        })
+    })
+}
+
+/// Parses date into `[year, month, day, hour, minute, second]`, validating ranges
+fn parse_date(value: &str) -> Option<[i32; 6]> {
+    if value.is_empty() {
+        return Some([1, 1, 1, 0, 0, 0]);
+    }
+    let (date, time) = value.split_once('T').unwrap_or((value, "00:00:00"));
+    let parse_parts = |part: &str, separator: char, lengths: [usize; 3]| -> Option<[i32; 3]> {
+        let mut parts = part.split(separator);
+        let mut result = [0; 3];
+        for (value, len) in result.iter_mut().zip(lengths) {
+            let part = parts.next()?;
+            if part.len() != len || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            *value = part.parse().ok()?;
+        }
+        parts.next().is_none().then_some(result)
+    };
+    let [year, month, day] = parse_parts(date, '-', [4, 2, 2])?;
+    let [hour, min, sec] = parse_parts(time, ':', [2, 2, 2])?;
+
+    let valid = year >= 1
+        && (1..=12).contains(&month)
+        && (1..=days_in_month(year, month)).contains(&day)
+        && hour < 24
+        && min < 60
+        && sec < 60;
+    valid.then_some([year, month, day, hour, min, sec])
+}
+
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Checks `Blob` default: byte string, e.g. `b"\x01\x02"`, or array of bytes, e.g. `[1, 2]`,
+/// and converts it into `Vec<u8>` expression
+fn blob_default_tkn(expr: &syn::Expr) -> darling::Result<TokenStream> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::ByteStr(_),
+            ..
+        })
+        | syn::Expr::Array(_) => Ok(quote! { #expr.to_vec() }),
+        _ => Err(darling::Error::custom(
+            "`Blob` default must be a byte string, e.g. `b\"\"`, or an array of bytes",
+        )
+        .with_span(expr)),
+    }
+}
+
+/// Returns `true` if type is `()`
+fn is_unit_type(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Tuple(tuple) if tuple.elems.is_empty())
+}
+
+/// Returns `T` if type is `Result<T, E>`
+fn result_ok_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let last_segment = path.path.segments.last()?;
+    if last_segment.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
     }
 }
 
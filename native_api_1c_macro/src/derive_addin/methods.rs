@@ -0,0 +1,94 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, ImplItem, ItemImpl};
+
+use super::{
+    functions::{generate::method_table_tkn, FuncDesc},
+    names::{const_items, validate_names},
+    static_generics,
+};
+
+/// Attributes of AddIn methods, that are removed from the impl block
+const METHOD_ATTRS: &[&str] = &["add_in_func", "arg", "returns"];
+
+pub fn add_in_methods(
+    attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let attr = TokenStream::from(attr);
+    let mut item_impl = parse_macro_input!(input as ItemImpl);
+    let functions = parse_methods(attr, &item_impl);
+
+    // attributes are removed even if parsing failed, so the only errors are the ones from parsing
+    for item in &mut item_impl.items {
+        if let ImplItem::Fn(item_fn) = item {
+            item_fn
+                .attrs
+                .retain(|attr| !METHOD_ATTRS.iter().any(|name| attr.path().is_ident(name)));
+        }
+    }
+
+    // on error, empty table is generated, so the struct derive doesn't fail too
+    let (method_table, error_tokens) = match functions.and_then(|functions| {
+        let names: Vec<_> = functions
+            .iter()
+            .map(|func| [&func.name, &func.name_ru])
+            .collect();
+        let names_check = validate_names(&names)?;
+        Ok((functions, names_check))
+    }) {
+        Ok((functions, names_check)) => (method_table_tkn(&functions), const_items(&names_check)),
+        Err(darling_error) => {
+            let error_tokens = darling_error.write_errors();
+            (quote! { &[] }, error_tokens)
+        }
+    };
+    let self_ty = &item_impl.self_ty;
+    let generics = static_generics(&item_impl.generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #item_impl
+
+        impl #impl_generics native_api_1c::native_api_1c_core::interface::descriptors::AddInMethods for #self_ty #where_clause {
+            const METHODS: &'static [
+                native_api_1c::native_api_1c_core::interface::descriptors::MethodDescriptor<Self>
+            ] = #method_table;
+        }
+
+        #error_tokens
+    }
+    .into()
+}
+
+fn parse_methods(attr: TokenStream, item_impl: &ItemImpl) -> Result<Vec<FuncDesc>, darling::Error> {
+    if !attr.is_empty() {
+        return Err(
+            darling::Error::custom("`add_in_methods` does not take arguments").with_span(&attr),
+        );
+    }
+    if let Some((_, trait_path, _)) = &item_impl.trait_ {
+        return Err(darling::Error::custom(
+            "`add_in_methods` must be used on inherent impl block, not trait impl",
+        )
+        .with_span(trait_path));
+    }
+
+    let mut functions = vec![];
+    for item in &item_impl.items {
+        let ImplItem::Fn(item_fn) = item else {
+            continue;
+        };
+        let has_add_in_func_attr = item_fn
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("add_in_func"));
+        if !has_add_in_func_attr {
+            continue;
+        }
+
+        functions.push(FuncDesc::from_impl_fn(item_fn)?);
+    }
+
+    Ok(functions)
+}
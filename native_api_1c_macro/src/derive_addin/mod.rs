@@ -1,15 +1,24 @@
+use darling::FromMeta;
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{parse_macro_input, spanned::Spanned, Attribute, DeriveInput};
 
-use functions::{collectors::*, parse::parse_functions};
-use props::{collectors::*, parse::parse_props};
+use flatten::{flatten_names_check_tkn, flatten_table_tkn, parse_flatten};
+use functions::{generate::method_table_tkn, parse::parse_functions};
+use names::{const_items, validate_names};
+use parsers::PropName;
+use props::{generate::prop_table_tkn, parse::parse_props};
+use settings::{load_settings_tkn, parse_settings, save_settings_tkn};
 use utils::{macros::tkn_err, str_literal_token};
 
 mod constants;
+mod flatten;
 mod functions;
+pub mod methods;
+pub mod names;
 mod parsers;
 mod props;
+mod settings;
 mod utils;
 
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -21,19 +30,42 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 }
 
 fn derive_result(input: &DeriveInput) -> Result<TokenStream, TokenStream> {
+    // marker is generated even if the derive fails, so `#[add_in_methods]` doesn't fail too
+    let impl_methods_marker = impl_methods_marker_tkn(input);
+    // `write_errors` produces `compile_error!` for each error
     let impl_block = build_impl_block(input).map_err(|darling_error| {
-        let error_tokens = darling_error.write_errors();
-        let error_tokens = quote! {
-            compile_error!(#error_tokens);
-        };
-        error_tokens
+        let errors = darling_error.write_errors();
+        quote! {
+            #errors
+            #impl_methods_marker
+        }
     })?;
 
     Ok(quote! {
         #impl_block
+        #impl_methods_marker
     })
 }
 
+/// `ImplMethods` for structs with `#[add_in(impl_methods)]`, required by `#[add_in_methods]`
+fn impl_methods_marker_tkn(input: &DeriveInput) -> TokenStream {
+    let impl_methods = parse_add_in_meta(&input.attrs)
+        .map(|add_in_meta| add_in_meta.impl_methods.is_some())
+        .unwrap_or_default();
+    if !impl_methods {
+        return quote! {};
+    }
+    let struct_ident = &input.ident;
+    let generics = static_generics(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics native_api_1c::native_api_1c_core::interface::descriptors::ImplMethods
+            for #struct_ident #ty_generics #where_clause
+        {
+        }
+    }
+}
+
 fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, darling::Error> {
     let struct_ident = &input.ident;
     let syn::Data::Struct(struct_data) = &input.data else {
@@ -42,59 +74,284 @@ fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, dar
             &struct_ident.span()
         );
     };
-    let add_in_name_literal = str_literal_token(&struct_ident.to_string(), struct_ident)?;
-
-    let props = parse_props(struct_data)?;
+    let add_in_meta = parse_add_in_meta(&input.attrs)?;
+    let add_in_name = match &add_in_meta.name {
+        Some(name) if name.value().is_empty() => {
+            return tkn_err!("AddIn name can't be empty", name);
+        }
+        Some(name) => name.value(),
+        None => struct_ident.to_string(),
+    };
+    let add_in_name_literal = str_literal_token(&add_in_name, struct_ident)?;
+    let props = parse_props(&input.attrs, struct_data)?;
     let functions = parse_functions(struct_data)?;
+    let flattened = parse_flatten(struct_data)?;
+    let settings = parse_settings(struct_data)?;
+    let (set_connection, connection_fn) = connection_tkn(struct_data)?;
+    if let (Some(setting), true) = (settings.first(), connection_fn.is_empty()) {
+        return tkn_err!(
+            "`add_in_setting` fields are saved in `done`, so struct must have `add_in_con` field",
+            &setting.key
+        );
+    }
 
-    let pi = props.iter().enumerate();
-    let prop_definitions = [
-        pi.clone().collect::<FindPropCollector>().release()?,
-        pi.clone().collect::<GetNPropsCollector>().release()?,
-        pi.clone().collect::<GetPropNameCollector>().release()?,
-        pi.clone().collect::<IsPropReadableCollector>().release()?,
-        pi.clone().collect::<IsPropWritableCollector>().release()?,
-        pi.clone().collect::<GetPropValCollector>().release()?,
-        pi.clone().collect::<SetPropValCollector>().release()?,
-    ];
-
-    let fi = functions.iter().enumerate();
-    let func_definitions = [
-        fi.clone().collect::<FindMethodCollector>().release()?,
-        fi.clone().collect::<GetMethodNameCollector>().release()?,
-        fi.clone().collect::<GetNMethodsCollector>().release()?,
-        fi.clone().collect::<GetNParamsCollector>().release()?,
-        fi.clone().collect::<HasReturnValueCollector>().release()?,
-        fi.clone().collect::<CallAsProcCollector>().release()?,
-        fi.clone().collect::<CallAsFuncCollector>().release()?,
-        fi.clone()
-            .collect::<GetParamDefValueCollector>()
-            .release()?,
-    ];
+    let prop_names = props.iter().map(|prop| [&prop.name, &prop.name_ru]);
+    let func_names = functions.iter().map(|func| [&func.name, &func.name_ru]);
+    let mut names_check = validate_names(&prop_names.chain(func_names).collect::<Vec<_>>())?;
 
-    let result = quote! {
-        impl native_api_1c::native_api_1c_core::interface::AddInWrapper for #struct_ident {
-            fn init(&mut self, interface: &'static native_api_1c::native_api_1c_core::ffi::connection::Connection) -> bool {
-                self.connection = std::sync::Arc::new(Some(interface));
-                true
-            }
+    let generics = static_generics(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-            fn get_info(&self) -> u16 {
-                2000
-            }
-            fn done(&mut self) {}
-            fn register_extension_as(&mut self) -> &[u16] {
-                &utf16_lit::utf16_null!(#add_in_name_literal)
-            }
+    let self_ty = quote! { #struct_ident #ty_generics };
+    names_check.extend(flatten_names_check_tkn(&self_ty, &flattened));
 
-            #(#prop_definitions)*
-            #(#func_definitions)*
+    let prop_table = prop_table_tkn(&props);
+    let method_table = if add_in_meta.impl_methods.is_some() {
+        if let Some(func) = functions.first() {
+            return tkn_err!(
+                "Methods are declared in `#[add_in_methods]` impl block, `add_in_func` fields are not allowed",
+                &func.ident
+            );
+        }
+        names_check.extend(methods_names_check_tkn(&self_ty, &props));
+        quote! {
+            <Self as native_api_1c::native_api_1c_core::interface::descriptors::AddInMethods>::METHODS
+        }
+    } else {
+        method_table_tkn(&functions)
+    };
+
+    let flatten_table = (!flattened.is_empty()).then(|| {
+        let flatten_table = flatten_table_tkn(&flattened);
+        quote! {
+            const FLATTENED: &'static [
+                native_api_1c::native_api_1c_core::interface::descriptors::FlattenDescriptor<Self>
+            ] = #flatten_table;
+        }
+    });
 
+    let init_hook = init_hook_tkn(&add_in_meta, &add_in_name_literal);
+    let profile = match &add_in_meta.profile {
+        Some(profile) if profile.value().is_empty() => {
+            return tkn_err!("Profile name can't be empty", profile);
+        }
+        Some(profile) => profile.to_token_stream(),
+        None => add_in_name_literal.clone(),
+    };
+    let load_settings = load_settings_tkn(&settings, &profile);
+    let save_settings = save_settings_tkn(&settings, &profile);
+    let done_fn = (add_in_meta.on_done.is_some() || !settings.is_empty()).then(|| {
+        let on_done = add_in_meta
+            .on_done
+            .as_ref()
+            .map(|on_done| quote! { (#on_done)(self); });
+        quote! {
+            fn done(&mut self) {
+                #on_done
+                #save_settings
+            }
+        }
+    });
+    let set_locale_fn = add_in_meta.on_locale.as_ref().map(|on_locale| {
+        quote! {
             fn set_locale(&mut self, loc: &[u16]) {
+                let loc = native_api_1c::native_api_1c_core::ffi::string_utils::from_os_string(loc);
+                (#on_locale)(self, &loc)
             }
+        }
+    });
+    let set_language_fn = add_in_meta.on_language.as_ref().map(|on_language| {
+        quote! {
             fn set_user_interface_language_code(&mut self, lang: &[u16]) {
+                let lang = native_api_1c::native_api_1c_core::ffi::string_utils::from_os_string(lang);
+                (#on_language)(self, &lang)
+            }
+        }
+    });
+
+    // generic struct can't be named outside of its impl, so assertions are evaluated in `init`
+    // when it is instantiated
+    let (names_check_items, names_check_stmts) = if input.generics.params.is_empty() {
+        (const_items(&names_check), quote! {})
+    } else {
+        (quote! {}, quote! { #(const { #names_check };)* })
+    };
+
+    let result = quote! {
+        impl #impl_generics native_api_1c::native_api_1c_core::interface::descriptors::AddInDescriptor
+            for #struct_ident #ty_generics #where_clause
+        {
+            const EXTENSION_NAME: &'static [u16] = &utf16_lit::utf16_null!(#add_in_name_literal);
+
+            const PROPS: &'static [
+                native_api_1c::native_api_1c_core::interface::descriptors::PropDescriptor<Self>
+            ] = #prop_table;
+
+            const METHODS: &'static [
+                native_api_1c::native_api_1c_core::interface::descriptors::MethodDescriptor<Self>
+            ] = #method_table;
+
+            #flatten_table
+
+            fn init(&mut self, interface: &'static native_api_1c::native_api_1c_core::ffi::connection::Connection) -> bool {
+                #names_check_stmts
+                #set_connection
+                #load_settings
+                #init_hook
             }
+
+            #connection_fn
+
+            #done_fn
+
+            #set_locale_fn
+
+            #set_language_fn
         }
+
+        #names_check_items
     };
     Ok(result)
 }
+
+/// Generics of the AddIn with `Self: 'static` bound, required by `AddInDescriptor` and
+/// `AddInMethods`, so structs with lifetimes are only implemented for `'static`
+fn static_generics(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+    if !generics.params.is_empty() {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { Self: 'static });
+    }
+    generics
+}
+
+/// Assertions, that properties don't reuse names of methods from `#[add_in_methods]` impl block.
+/// Methods are parsed by another macro, so names are compared in const context
+fn methods_names_check_tkn(self_ty: &TokenStream, props: &[props::PropDesc]) -> Vec<TokenStream> {
+    let descriptors = quote! { native_api_1c::native_api_1c_core::interface::descriptors };
+    props
+        .iter()
+        .flat_map(|prop| [&prop.name, &prop.name_ru])
+        .map(|name| {
+            let message = match name {
+                PropName::StringLiteral(lit) => lit.value(),
+                PropName::Ident(path) => path.to_token_stream().to_string(),
+            };
+            let message = format!("property name `{message}` is already used by a method");
+            quote_spanned! { name.span() =>
+                assert!(
+                    !#descriptors::methods_have_name(
+                        <#self_ty as #descriptors::AddInMethods>::METHODS,
+                        #name,
+                    ),
+                    #message,
+                )
+            }
+        })
+        .collect()
+}
+
+/// Finds the field marked with `#[add_in_con]`, returns code storing the connection in it
+/// and `connection` function reading it. The field is optional
+fn connection_tkn(
+    struct_data: &syn::DataStruct,
+) -> Result<(TokenStream, TokenStream), darling::Error> {
+    let mut connection_field = None;
+    for (index, field) in struct_data.fields.iter().enumerate() {
+        let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("add_in_con"))
+        else {
+            continue;
+        };
+        if let Err(err) = attr.meta.require_path_only() {
+            return Err(err.into());
+        }
+        if connection_field.is_some() {
+            return tkn_err!("Struct can have only 1 `add_in_con` field", attr);
+        }
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+        connection_field = Some((member, &field.ty));
+    }
+
+    let Some((member, ty)) = connection_field else {
+        return Ok((quote! { let _ = interface; }, quote! {}));
+    };
+    let set_connection = quote_spanned! { ty.span() =>
+        native_api_1c::native_api_1c_core::interface::ConnectionField::set_connection(
+            &mut self.#member,
+            interface,
+        );
+    };
+    let get_connection = quote_spanned! { ty.span() =>
+        native_api_1c::native_api_1c_core::interface::ConnectionField::connection(&self.#member)
+    };
+    let connection_fn = quote! {
+        fn connection(&self) -> Option<&'static native_api_1c::native_api_1c_core::ffi::connection::Connection> {
+            #get_connection
+        }
+    };
+    Ok((set_connection, connection_fn))
+}
+
+fn init_hook_tkn(add_in_meta: &AddInMeta, add_in_name_literal: &TokenStream) -> TokenStream {
+    let Some(on_init) = &add_in_meta.on_init else {
+        return quote! { true };
+    };
+    // error is shown in 1C, and the AddIn fails to load
+    quote! {
+        match (#on_init)(self) {
+            Ok(()) => true,
+            Err(err) => {
+                interface.add_error(
+                    native_api_1c::native_api_1c_core::ffi::connection::MessageCode::Fail,
+                    #add_in_name_literal,
+                    &err.to_string(),
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Options of the AddIn, set with `#[add_in(...)]` on the struct
+/// # Fields
+/// * `name` - name of the AddIn in 1C, struct name by default
+/// * `impl_methods` - methods are declared in impl block with `#[add_in_methods]`
+/// * `on_init` - `fn(&mut Self) -> Result<(), E>`, called after the connection is set,
+///   `E: Display` is shown in 1C and the AddIn fails to load
+/// * `on_done` - `fn(&mut Self)`, called before the AddIn is unloaded
+/// * `on_locale` - `fn(&mut Self, &str)`, called on `SetLocale` with locale, e.g. `ru_RU`,
+///   platform calls it before 8.3.21 only
+/// * `on_language` - `fn(&mut Self, &str)`, called on `SetUserInterfaceLanguageCode` with
+///   language code of the interface, e.g. `ru`, since 8.3.21
+/// * `profile` - name of the profile, where `add_in_setting` fields are stored, AddIn name
+///   by default
+#[derive(FromMeta, Debug, Default)]
+struct AddInMeta {
+    name: Option<syn::LitStr>,
+    profile: Option<syn::LitStr>,
+    impl_methods: Option<()>,
+    on_init: Option<syn::Expr>,
+    on_done: Option<syn::Expr>,
+    on_locale: Option<syn::Expr>,
+    on_language: Option<syn::Expr>,
+}
+
+fn parse_add_in_meta(attrs: &[Attribute]) -> Result<AddInMeta, darling::Error> {
+    let add_in_attrs: Vec<&Attribute> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("add_in"))
+        .collect();
+    match add_in_attrs.as_slice() {
+        [] => Ok(AddInMeta::default()),
+        [attr] => AddInMeta::from_meta(&attr.meta),
+        [_, attr, ..] => tkn_err!("Struct can have only 1 `add_in` attribute", attr),
+    }
+}
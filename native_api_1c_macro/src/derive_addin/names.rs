@@ -0,0 +1,107 @@
+use native_api_1c_core::interface::descriptors::names_equal;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+
+use super::parsers::PropName;
+
+pub const NAME_RULES: &str = "it must be non-empty, start with a letter or `_` and contain only \
+    letters of Latin, Greek and Cyrillic alphabets, digits and `_`";
+
+/// Checks that name can be used as identifier in 1C, the same function checks names,
+/// set with constants, in const assertions
+pub use native_api_1c_core::interface::descriptors::is_valid_name;
+
+/// Checks names of the AddIn members, each member has English and Russian name.
+/// Names must be valid identifiers and must not repeat across members, ignoring case,
+/// as 1C looks members up by any of them. Literal names are checked here, names set with
+/// constants can't be read by the macro, so `assert!` expressions are returned for them,
+/// see `const_items`
+pub fn validate_names(members: &[[&PropName; 2]]) -> darling::Result<Vec<TokenStream>> {
+    let mut errors = darling::Error::accumulator();
+    let mut seen_names: Vec<(usize, &syn::LitStr)> = vec![];
+    let mut assertions = vec![];
+
+    for (member_index, names) in members.iter().enumerate() {
+        for name in names {
+            let lit = match name {
+                PropName::StringLiteral(lit) => lit,
+                PropName::Ident(path) => {
+                    assertions.extend(const_assertions(path, member_index, members));
+                    continue;
+                }
+            };
+
+            let value = lit.value();
+            if !is_valid_name(&value) {
+                errors.push(
+                    darling::Error::custom(format!("invalid name `{value}`: {NAME_RULES}"))
+                        .with_span(lit),
+                );
+                continue;
+            }
+            let seen = seen_names
+                .iter()
+                .find(|(_, seen)| names_equal(&seen.value(), &value));
+            match seen {
+                // English and Russian names of the same member can be the same
+                Some((first_index, _)) if *first_index == member_index => {}
+                Some((_, first)) => {
+                    errors.push(
+                        darling::Error::custom(format!("duplicate name `{value}`")).with_span(lit),
+                    );
+                    errors.push(
+                        darling::Error::custom(format!(
+                            "name `{}` is first used here",
+                            first.value()
+                        ))
+                        .with_span(*first),
+                    );
+                }
+                None => {
+                    seen_names.push((member_index, lit));
+                }
+            }
+        }
+    }
+
+    errors.finish_with(assertions)
+}
+
+/// Wraps each assertion in its own const item, so all failed ones are reported
+pub fn const_items(assertions: &[TokenStream]) -> TokenStream {
+    quote! { #(const _: () = #assertions;)* }
+}
+
+/// Assertions, that name constant is valid and differs from names of other members.
+/// Pairs of constants are checked once, by the constant of the later member
+fn const_assertions(
+    path: &syn::ExprPath,
+    member_index: usize,
+    members: &[[&PropName; 2]],
+) -> Vec<TokenStream> {
+    let descriptors = quote! { native_api_1c::native_api_1c_core::interface::descriptors };
+    let path_str = path.to_token_stream().to_string();
+
+    let invalid_message = format!("invalid name in `{path_str}`: {NAME_RULES}");
+    let mut assertions = vec![quote_spanned! { path.span() =>
+        assert!(#descriptors::is_valid_name(#path), #invalid_message)
+    }];
+    for (other_index, other_names) in members.iter().enumerate() {
+        if other_index == member_index {
+            continue;
+        }
+        for other in other_names {
+            let other_str = match other {
+                PropName::Ident(_) if other_index > member_index => continue,
+                PropName::Ident(other_path) => other_path.to_token_stream().to_string(),
+                PropName::StringLiteral(lit) => lit.value(),
+            };
+            let duplicate_message = format!("duplicate name in `{path_str}` and `{other_str}`");
+            assertions.push(quote_spanned! { path.span() =>
+                assert!(!#descriptors::names_equal(#path, #other), #duplicate_message)
+            });
+        }
+    }
+    assertions
+}
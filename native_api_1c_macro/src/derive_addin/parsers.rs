@@ -2,16 +2,38 @@ use darling::FromMeta;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
-use super::constants::{BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, I32_TYPE, STRING_TYPE};
+use super::{
+    constants::{
+        ANY_TYPE, BLOB_TYPE, BOOL_TYPE, CUSTOM_TYPE, DATE_TYPE, DECIMAL_TYPE, F64_TYPE, I16_TYPE,
+        I32_TYPE, I64_TYPE, JSON_TYPE, STRING_TYPE, U32_TYPE, U64_TYPE, U8_TYPE, USIZE_TYPE,
+    },
+    utils::option_inner_type,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ParamType {
     Bool,
     I32,
+    /// Integer types other than `i32`, passed to 1C as `I32` if value fits,
+    /// range-checked when received from 1C
+    I16,
+    I64,
+    U8,
+    U32,
+    U64,
+    Usize,
     F64,
+    /// `rust_decimal::Decimal`, passed to 1C as string, requires `decimal` feature
+    Decimal,
     String,
     Date,
     Blob,
+    /// Raw `ParamValue` of any type, passed through without conversion
+    Any,
+    /// `Json<T>`, passed to 1C as JSON string, requires `serde` feature
+    Json,
+    /// Any Rust type, implementing `FromParamValue` and `IntoParamValue`
+    Custom,
 }
 
 const META_TYPE_ERR: &str = "expected string literal or path";
@@ -32,11 +54,13 @@ impl FromMeta for ParamType {
 
     fn from_string(value: &str) -> darling::Result<Self> {
         let joined_allowed_types = crate::derive_addin::constants::ALL_ARG_TYPES.join(", ");
-        Self::try_from(value).map_err(|_| {
-            darling::Error::custom(format!(
-                "unknown type `{value}`. Must be one of: {joined_allowed_types}",
-            ))
-        })
+        Self::try_from(value)
+            .map_err(|_| {
+                darling::Error::custom(format!(
+                    "unknown type `{value}`. Must be one of: {joined_allowed_types}",
+                ))
+            })?
+            .check_enabled()
     }
 }
 
@@ -47,10 +71,20 @@ impl TryFrom<&str> for ParamType {
         match value {
             BOOL_TYPE => Ok(ParamType::Bool),
             I32_TYPE => Ok(ParamType::I32),
+            I16_TYPE => Ok(ParamType::I16),
+            I64_TYPE => Ok(ParamType::I64),
+            U8_TYPE => Ok(ParamType::U8),
+            U32_TYPE => Ok(ParamType::U32),
+            U64_TYPE => Ok(ParamType::U64),
+            USIZE_TYPE => Ok(ParamType::Usize),
             F64_TYPE => Ok(ParamType::F64),
+            DECIMAL_TYPE => Ok(ParamType::Decimal),
             STRING_TYPE => Ok(ParamType::String),
             DATE_TYPE => Ok(ParamType::Date),
             BLOB_TYPE => Ok(ParamType::Blob),
+            ANY_TYPE => Ok(ParamType::Any),
+            JSON_TYPE => Ok(ParamType::Json),
+            CUSTOM_TYPE => Ok(ParamType::Custom),
             _ => Err(()),
         }
     }
@@ -77,11 +111,209 @@ impl ToTokens for ParamType {
             ParamType::Blob => {
                 quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Blob }
             }
+            ParamType::I16
+            | ParamType::I64
+            | ParamType::U8
+            | ParamType::U32
+            | ParamType::U64
+            | ParamType::Usize
+            | ParamType::Decimal
+            | ParamType::Any
+            | ParamType::Json
+            | ParamType::Custom => {
+                quote! { native_api_1c::native_api_1c_core::interface::ParamValue }
+            }
+        }
+    }
+}
+
+impl ParamType {
+    /// Returns Rust type for integer types, that are converted with range checks
+    pub fn integer_type(&self) -> Option<TokenStream> {
+        match self {
+            ParamType::I16 => Some(quote! { i16 }),
+            ParamType::I64 => Some(quote! { i64 }),
+            ParamType::U8 => Some(quote! { u8 }),
+            ParamType::U32 => Some(quote! { u32 }),
+            ParamType::U64 => Some(quote! { u64 }),
+            ParamType::Usize => Some(quote! { usize }),
+            _ => None,
+        }
+    }
+
+    /// Returns error if type requires cargo feature, that is not enabled
+    pub fn check_enabled(self) -> darling::Result<Self> {
+        if self == ParamType::Decimal && !cfg!(feature = "decimal") {
+            return Err(darling::Error::custom(
+                "`Decimal` type requires `decimal` feature of `native_api_1c`",
+            ));
+        }
+        if self == ParamType::Json && !cfg!(feature = "serde") {
+            return Err(darling::Error::custom(
+                "`Json` type requires `serde` feature of `native_api_1c`",
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Returns `true` for types, converted by `FromParamValue` and `IntoParamValue`,
+    /// with Rust type taken from the function signature or the field
+    pub fn uses_traits(&self) -> bool {
+        matches!(self, ParamType::Custom | ParamType::Json)
+    }
+
+    /// Returns name of the type, as it is set in attributes, e.g. `Int`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParamType::Bool => BOOL_TYPE,
+            ParamType::I32 => I32_TYPE,
+            ParamType::I16 => I16_TYPE,
+            ParamType::I64 => I64_TYPE,
+            ParamType::U8 => U8_TYPE,
+            ParamType::U32 => U32_TYPE,
+            ParamType::U64 => U64_TYPE,
+            ParamType::Usize => USIZE_TYPE,
+            ParamType::F64 => F64_TYPE,
+            ParamType::Decimal => DECIMAL_TYPE,
+            ParamType::String => STRING_TYPE,
+            ParamType::Date => DATE_TYPE,
+            ParamType::Blob => BLOB_TYPE,
+            ParamType::Any => ANY_TYPE,
+            ParamType::Json => JSON_TYPE,
+            ParamType::Custom => CUSTOM_TYPE,
+        }
+    }
+
+    /// Name of the type in 1C, used in conversion error messages
+    pub fn platform_name(&self) -> &'static str {
+        match self {
+            ParamType::Bool => "Boolean",
+            ParamType::String | ParamType::Json => "String",
+            ParamType::Date => "Date",
+            ParamType::Blob => "BinaryData",
+            ParamType::Any | ParamType::Custom => "Arbitrary",
+            _ => "Number",
+        }
+    }
+
+    /// Infers type from Rust type in the function signature, references and `Option<T>`
+    /// are skipped. Returns `None` for types, that can't be recognized, e.g. aliases
+    pub fn infer(ty: &syn::Type) -> Option<Self> {
+        if let Some(inner) = option_inner_type(ty) {
+            return Self::infer(inner);
+        }
+        let path = match ty {
+            syn::Type::Reference(reference) => return Self::infer(&reference.elem),
+            syn::Type::Paren(paren) => return Self::infer(&paren.elem),
+            syn::Type::Path(path) => path,
+            _ => return None,
+        };
+        let last_segment = path.path.segments.last()?;
+        let inferred = match last_segment.ident.to_string().as_str() {
+            "bool" => ParamType::Bool,
+            "i32" => ParamType::I32,
+            "i16" => ParamType::I16,
+            "i64" => ParamType::I64,
+            "u8" => ParamType::U8,
+            "u32" => ParamType::U32,
+            "u64" => ParamType::U64,
+            "usize" => ParamType::Usize,
+            "f64" => ParamType::F64,
+            "Decimal" => ParamType::Decimal,
+            "String" => ParamType::String,
+            "NaiveDateTime" | "Tm" => ParamType::Date,
+            "ParamValue" => ParamType::Any,
+            "Json" => ParamType::Json,
+            "Vec" => {
+                let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+                    return None;
+                };
+                match args.args.first()? {
+                    syn::GenericArgument::Type(inner)
+                        if inner.to_token_stream().to_string() == "u8" =>
+                    {
+                        ParamType::Blob
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some(inferred)
+    }
+
+    /// Returns `true` for integer types, that can be larger than `i64::MAX`
+    pub fn is_wide_unsigned(&self) -> bool {
+        matches!(self, ParamType::U64 | ParamType::Usize)
+    }
+}
+
+/// Conversions of values received from 1C, see `Coercion` in `native_api_1c_core`
+/// # Fields
+/// * `strict` - no conversions at all, set by `strict`
+/// * `bool_int` - `Bool` and integers are converted to each other, set by `coerce_bool`
+/// * `to_string` - numbers and dates are converted to strings, set by `coerce_string`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CoercionDesc {
+    pub strict: bool,
+    pub bool_int: bool,
+    pub to_string: bool,
+}
+
+impl CoercionDesc {
+    pub fn from_flags(
+        strict: Option<()>,
+        coerce_bool: Option<()>,
+        coerce_string: Option<()>,
+    ) -> Result<Self, (&'static str, &'static str)> {
+        if strict.is_some() && coerce_bool.is_some() {
+            return Err(("strict", "coerce_bool"));
         }
+        if strict.is_some() && coerce_string.is_some() {
+            return Err(("strict", "coerce_string"));
+        }
+        Ok(Self {
+            strict: strict.is_some(),
+            bool_int: coerce_bool.is_some(),
+            to_string: coerce_string.is_some(),
+        })
     }
 }
 
-#[derive(Debug)]
+impl CoercionDesc {
+    /// Returns name of the flag, that has no effect for the type, e.g. `coerce_string` for
+    /// `Int`. `Custom` accepts all flags, they are passed to its `FromParamValue`
+    pub fn unsupported_flag(&self, ty: &ParamType) -> Option<&'static str> {
+        let is_integer = *ty == ParamType::I32 || ty.integer_type().is_some();
+        if self.strict && !(is_integer || matches!(ty, ParamType::F64 | ParamType::Custom)) {
+            return Some("strict");
+        }
+        if self.bool_int && !(is_integer || matches!(ty, ParamType::Bool | ParamType::Custom)) {
+            return Some("coerce_bool");
+        }
+        if self.to_string && !matches!(ty, ParamType::String | ParamType::Custom) {
+            return Some("coerce_string");
+        }
+        None
+    }
+}
+
+impl ToTokens for CoercionDesc {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let numbers = !self.strict;
+        let bool_int = self.bool_int;
+        let to_string = self.to_string;
+        tokens.extend(quote! {
+            native_api_1c::native_api_1c_core::interface::Coercion {
+                numbers: #numbers,
+                bool_int: #bool_int,
+                to_string: #to_string,
+            }
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum PropName {
     StringLiteral(syn::LitStr),
     Ident(syn::ExprPath),
@@ -100,11 +332,11 @@ impl FromMeta for PropName {
     }
 }
 
-impl From<PropName> for proc_macro2::TokenStream {
-    fn from(prop_name: PropName) -> proc_macro2::TokenStream {
-        match prop_name {
-            PropName::StringLiteral(str_lit) => str_lit.to_token_stream(),
-            PropName::Ident(ident) => ident.to_token_stream(),
+impl ToTokens for PropName {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            PropName::StringLiteral(str_lit) => str_lit.to_tokens(tokens),
+            PropName::Ident(ident) => ident.to_tokens(tokens),
         }
     }
 }
@@ -1 +1,120 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 
+use crate::derive_addin::utils::{
+    expr_from_os_value, optional_expr_from_os_value, try_expr_to_os_value,
+};
+
+use super::{PropAccessor, PropDesc};
+
+pub fn prop_table_tkn(props: &[PropDesc]) -> TokenStream {
+    let descriptors = props.iter().map(prop_descriptor_tkn);
+    quote! {
+        &[#(#descriptors),*]
+    }
+}
+
+fn prop_descriptor_tkn(prop: &PropDesc) -> TokenStream {
+    let name = &prop.name;
+    let name_ru = &prop.name_ru;
+
+    let getter = match &prop.getter {
+        Some(accessor) => {
+            let prop_getter = prop_getter_tkn(prop, accessor);
+            quote! { Some(|addin| { #prop_getter }) }
+        }
+        None => quote! { None },
+    };
+
+    let setter = match &prop.setter {
+        Some(accessor) => {
+            let prop_setter = prop_setter_tkn(prop, accessor);
+            quote! { Some(|addin, val| { #prop_setter }) }
+        }
+        None => quote! { None },
+    };
+
+    quote! {
+        native_api_1c::native_api_1c_core::interface::descriptors::PropDescriptor {
+            name: #name,
+            name_ru: #name_ru,
+            getter: #getter,
+            setter: #setter,
+        }
+    }
+}
+
+/// Reports error of the property to 1C and returns `()`
+/// # Arguments
+/// * `span` - span of the function, so that error of `E` not implementing
+///   `Display` points to it
+fn report_error_tkn(prop: &PropDesc, addin: TokenStream, span: Span) -> TokenStream {
+    let name = &prop.name;
+    quote_spanned! { span =>
+        |err| native_api_1c::native_api_1c_core::interface::descriptors::report_prop_error(
+            #addin, #name, &err,
+        )
+    }
+}
+
+fn prop_getter_tkn(prop: &PropDesc, accessor: &PropAccessor) -> TokenStream {
+    let read_value = match accessor {
+        PropAccessor::Field(ident) => quote! {
+            let prop_val = &addin.#ident;
+        },
+        PropAccessor::Function { func, result } => {
+            let report_error = report_error_tkn(prop, quote! { addin }, func.span());
+            let map_err = result.then(|| quote! { .map_err(#report_error)? });
+            quote! {
+                let prop_val = (#func)(addin)#map_err;
+            }
+        }
+    };
+    let prop_val = try_expr_to_os_value(&quote! { prop_val }, &prop.ty, false, prop.optional);
+    // conversion errors are reported like errors of the getter
+    let report_error = report_error_tkn(prop, quote! { addin }, Span::call_site());
+    quote! {
+        #read_value
+        #prop_val.map_err(#report_error)
+    }
+}
+
+fn prop_setter_tkn(prop: &PropDesc, accessor: &PropAccessor) -> TokenStream {
+    // conversion errors are reported like errors of the setter
+    let report_error = report_error_tkn(prop, quote! { &*addin }, Span::call_site());
+    let prop_val = if prop.ty.uses_traits() {
+        // custom type is inferred from the field or function, so no `.into()`
+        let prop_val = expr_from_os_value(&quote! { val }, &prop.ty, &prop.coercion);
+        quote! { #prop_val.map_err(#report_error)? }
+    } else if prop.optional {
+        let prop_val = optional_expr_from_os_value(&quote! { val }, &prop.ty, &prop.coercion);
+        quote! { #prop_val.map_err(#report_error)?.map(Into::into) }
+    } else {
+        let prop_val = expr_from_os_value(&quote! { val }, &prop.ty, &prop.coercion);
+        quote! { #prop_val.map_err(#report_error)?.into() }
+    };
+    let write_value = match accessor {
+        PropAccessor::Field(ident) => quote! {
+            addin.#ident = prop_val;
+        },
+        PropAccessor::Function { func, result: true } => {
+            let report_error = report_error_tkn(prop, quote! { &*addin }, func.span());
+            quote! {
+                (#func)(addin, prop_val).map_err(#report_error)?;
+            }
+        }
+        // without `result` setter must return `()`, so that its `Result` is not dropped
+        PropAccessor::Function {
+            func,
+            result: false,
+        } => quote_spanned! { func.span() =>
+            let () = (#func)(addin, prop_val);
+        },
+    };
+    quote! {
+        let prop_val = #prop_val;
+        #write_value
+        Ok(())
+    }
+}
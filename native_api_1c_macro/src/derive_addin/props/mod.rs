@@ -1,19 +1,30 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::Ident;
 
-use super::parsers::ParamType;
+use super::parsers::{CoercionDesc, ParamType, PropName};
 
-pub mod collectors;
 pub mod generate;
 pub mod parse;
 
 #[derive(Debug)]
 pub struct PropDesc {
-    pub ident: Ident,
+    pub name: PropName,
+    pub name_ru: PropName,
 
-    pub name_literal: TokenStream,
-    pub name_ru_literal: TokenStream,
-
-    pub readable: bool,
-    pub writable: bool,
+    pub getter: Option<PropAccessor>,
+    pub setter: Option<PropAccessor>,
     pub ty: ParamType,
+    /// Property is `Option<T>`, `None` is passed to 1C as `Undefined`
+    pub optional: bool,
+    /// Conversions of values, written from 1C
+    pub coercion: CoercionDesc,
+}
+
+/// Way the derived code reads or writes the property
+#[derive(Debug)]
+pub enum PropAccessor {
+    /// Property is stored in the struct field
+    Field(Ident),
+    /// Property is computed by function, e.g. `get = Self::count`,
+    /// `result` is set if function returns `Result` with `E: Display`
+    Function { func: syn::Expr, result: bool },
 }
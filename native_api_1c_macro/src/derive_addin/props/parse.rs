@@ -2,9 +2,12 @@ use darling::{FromField, FromMeta};
 
 use syn::{Attribute, DataStruct};
 
-use crate::derive_addin::{parsers::PropName, utils::ident_option_to_darling_err};
+use crate::derive_addin::{
+    parsers::{CoercionDesc, PropName},
+    utils::{ident_option_to_darling_err, option_inner_type},
+};
 
-use super::{ParamType, PropDesc};
+use super::{ParamType, PropAccessor, PropDesc};
 
 impl FromField for PropDesc {
     fn from_field(field: &syn::Field) -> darling::Result<Self> {
@@ -29,16 +32,84 @@ impl FromField for PropDesc {
         let add_in_prop_attr = add_in_prop_attr[0];
 
         let prop_meta = PropMeta::from_meta(&add_in_prop_attr.meta)?;
+        let coercion = prop_meta.coercion(add_in_prop_attr)?;
+        prop_meta.check_result(add_in_prop_attr)?;
+        let (get_result, set_result) = (prop_meta.get_result(), prop_meta.set_result());
+        let field_accessor = || PropAccessor::Field(field_ident.clone());
+
+        let getter = match prop_meta.get {
+            Some(func) => Some(PropAccessor::Function {
+                func,
+                result: get_result,
+            }),
+            None => prop_meta.readable.map(|_| field_accessor()),
+        };
+        let setter = match prop_meta.set {
+            Some(func) => Some(PropAccessor::Function {
+                func,
+                result: set_result,
+            }),
+            None => prop_meta.writable.map(|_| field_accessor()),
+        };
+
+        // `Option<T>` field makes the property optional, even without `optional` in the attribute
+        let uses_field = matches!(getter, Some(PropAccessor::Field(_)))
+            || matches!(setter, Some(PropAccessor::Field(_)));
+        let optional =
+            prop_meta.optional.is_some() || (uses_field && option_inner_type(&field.ty).is_some());
 
         Ok(Self {
-            ident: field_ident.clone(),
+            name: prop_meta.name,
+            name_ru: prop_meta.name_ru,
 
-            name_literal: prop_meta.name.into(),
-            name_ru_literal: prop_meta.name_ru.into(),
+            getter,
+            setter,
+            ty: prop_meta.ty,
+            optional,
+            coercion,
+        })
+    }
+}
 
-            readable: prop_meta.readable.is_some(),
-            writable: prop_meta.writable.is_some(),
+impl PropDesc {
+    /// Parses property, declared with `add_in_prop` attribute on the struct itself.
+    /// Such property is not backed by a field, so it must have `get` and/or `set` function
+    pub fn from_struct_attr(attr: &Attribute) -> darling::Result<Self> {
+        let prop_meta = PropMeta::from_meta(&attr.meta)?;
+        let coercion = prop_meta.coercion(attr)?;
+
+        if prop_meta.readable.is_some() || prop_meta.writable.is_some() {
+            return Err(darling::Error::custom(
+                "Property without field is readable if it has `get` and writable if it has `set`, \
+                `readable` and `writable` are not allowed",
+            )
+            .with_span(attr));
+        }
+        if prop_meta.get.is_none() && prop_meta.set.is_none() {
+            return Err(darling::Error::custom(
+                "Property without field must have `get` and/or `set` function",
+            )
+            .with_span(attr));
+        }
+
+        prop_meta.check_result(attr)?;
+
+        let (get_result, set_result) = (prop_meta.get_result(), prop_meta.set_result());
+        Ok(Self {
+            name: prop_meta.name,
+            name_ru: prop_meta.name_ru,
+
+            getter: prop_meta.get.map(|func| PropAccessor::Function {
+                func,
+                result: get_result,
+            }),
+            setter: prop_meta.set.map(|func| PropAccessor::Function {
+                func,
+                result: set_result,
+            }),
             ty: prop_meta.ty,
+            optional: prop_meta.optional.is_some(),
+            coercion,
         })
     }
 }
@@ -50,9 +121,62 @@ pub struct PropMeta {
     pub name_ru: PropName,
     pub readable: Option<()>,
     pub writable: Option<()>,
+    pub get: Option<syn::Expr>,
+    pub set: Option<syn::Expr>,
+    pub result: Option<()>,
+    pub get_result: Option<()>,
+    pub set_result: Option<()>,
+    pub optional: Option<()>,
+    pub strict: Option<()>,
+    pub coerce_bool: Option<()>,
+    pub coerce_string: Option<()>,
 }
 
-pub fn parse_props(struct_data: &DataStruct) -> Result<Vec<PropDesc>, darling::Error> {
+impl PropMeta {
+    /// `get` function returns `Result`
+    fn get_result(&self) -> bool {
+        self.result.is_some() || self.get_result.is_some()
+    }
+
+    /// `set` function returns `Result`
+    fn set_result(&self) -> bool {
+        self.result.is_some() || self.set_result.is_some()
+    }
+
+    fn check_result(&self, attr: &Attribute) -> darling::Result<()> {
+        let error = if self.result.is_some() && self.get.is_none() && self.set.is_none() {
+            "`result` can only be set with `get` or `set` function"
+        } else if self.get_result.is_some() && self.get.is_none() {
+            "`get_result` can only be set with `get` function"
+        } else if self.set_result.is_some() && self.set.is_none() {
+            "`set_result` can only be set with `set` function"
+        } else {
+            return Ok(());
+        };
+        Err(darling::Error::custom(error).with_span(attr))
+    }
+
+    fn coercion(&self, attr: &Attribute) -> darling::Result<CoercionDesc> {
+        let coercion = CoercionDesc::from_flags(self.strict, self.coerce_bool, self.coerce_string)
+            .map_err(|(param1, param2)| {
+                darling::Error::custom(format!("Conflicting params: {param1} and {param2}"))
+                    .with_span(attr)
+            })?;
+        if let Some(flag) = coercion.unsupported_flag(&self.ty) {
+            return Err(darling::Error::custom(format!(
+                "`{flag}` has no effect for `{}` type",
+                self.ty.name()
+            ))
+            .with_span(attr));
+        }
+        Ok(coercion)
+    }
+}
+
+pub fn parse_props(
+    struct_attrs: &[Attribute],
+    struct_data: &DataStruct,
+) -> Result<Vec<PropDesc>, darling::Error> {
     let mut props = vec![];
 
     for field in &struct_data.fields {
@@ -68,5 +192,15 @@ pub fn parse_props(struct_data: &DataStruct) -> Result<Vec<PropDesc>, darling::E
         props.push(prop_desc);
     }
 
+    // properties without fields go after the ones stored in fields
+    for attr in struct_attrs {
+        if !attr.path().is_ident("add_in_prop") {
+            continue;
+        };
+
+        let prop_desc = PropDesc::from_struct_attr(attr)?;
+        props.push(prop_desc);
+    }
+
     Ok(props)
 }
@@ -0,0 +1,153 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, DataStruct};
+
+use super::utils::macros::tkn_err;
+
+/// Field, stored in the profile of the user, marked with `#[add_in_setting(...)]`
+pub struct SettingDesc {
+    pub member: syn::Member,
+    pub ty: syn::Type,
+    pub key: syn::LitStr,
+    pub default: Option<syn::Expr>,
+}
+
+/// Options of the setting
+/// # Fields
+/// * `key` - name of the value in the profile
+/// * `default` - value of the field, when the profile has no value, field keeps
+///   its initial value by default
+#[derive(FromMeta, Debug)]
+struct SettingMeta {
+    key: syn::LitStr,
+    #[darling(default, with = preserve_default)]
+    default: Option<syn::Expr>,
+}
+
+/// Keeps string literals as they are, so `default = "text"` is a string
+fn preserve_default(meta: &syn::Meta) -> darling::Result<Option<syn::Expr>> {
+    darling::util::parse_expr::preserve_str_literal(meta).map(Some)
+}
+
+pub fn parse_settings(struct_data: &DataStruct) -> Result<Vec<SettingDesc>, darling::Error> {
+    let mut settings: Vec<SettingDesc> = vec![];
+    for (index, field) in struct_data.fields.iter().enumerate() {
+        let attrs: Vec<_> = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("add_in_setting"))
+            .collect();
+        let attr = match attrs.as_slice() {
+            [] => continue,
+            [attr] => *attr,
+            [_, attr, ..] => {
+                return tkn_err!("Field can have only 1 `add_in_setting` attribute", attr)
+            }
+        };
+        let setting_meta = SettingMeta::from_meta(&attr.meta)?;
+        let key = setting_meta.key;
+        if key.value().is_empty() {
+            return tkn_err!("Setting key can't be empty", &key);
+        }
+        if settings
+            .iter()
+            .any(|setting| setting.key.value() == key.value())
+        {
+            return tkn_err!(format!("duplicate setting key `{}`", key.value()), &key);
+        }
+
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+        settings.push(SettingDesc {
+            member,
+            ty: field.ty.clone(),
+            key,
+            default: setting_meta.default,
+        });
+    }
+    Ok(settings)
+}
+
+/// Registers the profile and reads settings from it, called in `init`.
+/// Settings get default values, if the profile can't be registered, or has
+/// no value of the field type
+pub fn load_settings_tkn(settings: &[SettingDesc], profile: &TokenStream) -> TokenStream {
+    if settings.is_empty() {
+        return quote! {};
+    }
+    let loads = settings.iter().map(|setting| {
+        let SettingDesc {
+            member,
+            ty,
+            key,
+            default,
+        } = setting;
+        // string literal is converted, so `default = "text"` works for `String` fields
+        let default = default.as_ref().map(|default| match default {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(_),
+                ..
+            }) => quote! { self.#member = #default.into(); },
+            _ => quote! { self.#member = #default; },
+        });
+        let read = quote_spanned! { ty.span() =>
+            native_api_1c::native_api_1c_core::interface::descriptors::read_setting::<#ty>(
+                interface,
+                <Self as native_api_1c::native_api_1c_core::interface::descriptors::AddInDescriptor>::EXTENSION_NAME,
+                #key,
+            )
+        };
+        quote! {
+            match profile_registered.then(|| #read).flatten() {
+                Some(value) => self.#member = value,
+                None => {
+                    #default
+                }
+            }
+        }
+    });
+    quote! {
+        let profile_registered =
+            native_api_1c::native_api_1c_core::interface::descriptors::register_settings_profile(
+                interface,
+                <Self as native_api_1c::native_api_1c_core::interface::descriptors::AddInDescriptor>::EXTENSION_NAME,
+                #profile,
+            );
+        #(#loads)*
+    }
+}
+
+/// Registers the profile again and writes settings to it, called in `done`.
+/// Platform has one current profile, which nested AddIns with their own profiles
+/// change, so the one, registered in `init`, can't be relied on. Settings are not
+/// written, if the profile can't be registered
+pub fn save_settings_tkn(settings: &[SettingDesc], profile: &TokenStream) -> TokenStream {
+    if settings.is_empty() {
+        return quote! {};
+    }
+    let saves = settings.iter().map(|setting| {
+        let SettingDesc {
+            member, ty, key, ..
+        } = setting;
+        let into_value = quote_spanned! { ty.span() =>
+            native_api_1c::native_api_1c_core::interface::IntoParamValue::into_param_value(
+                ::core::clone::Clone::clone(&self.#member),
+            )
+        };
+        quote! {
+            connection.write(#key, &#into_value);
+        }
+    });
+    quote! {
+        if let Some(connection) =
+            native_api_1c::native_api_1c_core::interface::descriptors::AddInDescriptor::connection(self)
+        {
+            if connection.register_profile_as(#profile) {
+                #(#saves)*
+            }
+        }
+    }
+}
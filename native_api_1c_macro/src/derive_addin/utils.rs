@@ -2,7 +2,7 @@ use proc_macro2::{LexError, TokenStream};
 use quote::quote;
 use syn::{spanned::Spanned, Ident};
 
-use super::parsers::ParamType;
+use super::parsers::{CoercionDesc, ParamType};
 
 pub mod macros {
     macro_rules! tkn_err_inner {
@@ -55,6 +55,20 @@ pub fn expr_to_os_value(
     } else {
         quote! {native_api_1c::native_api_1c_core::ffi::string_utils::os_string}
     };
+    if let Some(integer_type) = ty.integer_type() {
+        let (from_fn, wide_type) = if ty.is_wide_unsigned() {
+            (quote! { from_u64 }, quote! { u64 })
+        } else {
+            (quote! { from_i64 }, quote! { i64 })
+        };
+        return quote! {
+            {
+                let _ = "expr_to_os_value: specific case for integers";
+                let val: #integer_type = #expr.clone();
+                native_api_1c::native_api_1c_core::interface::ParamValue::#from_fn(val as #wide_type)
+            }
+        };
+    }
     match ty {
         ParamType::String => quote! {
             {
@@ -62,6 +76,26 @@ pub fn expr_to_os_value(
                 #ty(#os_string_fn(&#expr.clone()).clone().into())
             }
         },
+        ParamType::Custom | ParamType::Json => quote! {
+            {
+                let _ = "expr_to_os_value: specific case for Custom";
+                native_api_1c::native_api_1c_core::interface::IntoParamValue::into_param_value(
+                    #expr.clone()
+                )
+            }
+        },
+        ParamType::Decimal => quote! {
+            {
+                let _ = "expr_to_os_value: specific case for Decimal";
+                native_api_1c::native_api_1c_core::interface::ParamValue::from_decimal(#expr.clone())
+            }
+        },
+        ParamType::Any => quote! {
+            {
+                let _ = "expr_to_os_value: specific case for Any";
+                native_api_1c::native_api_1c_core::interface::ParamValue::from(#expr.clone())
+            }
+        },
         _ => quote! {
             {
                 let _ = "expr_to_os_value: generic case";
@@ -71,40 +105,198 @@ pub fn expr_to_os_value(
     }
 }
 
-pub fn expr_from_os_value(expr: &TokenStream, ty: &ParamType) -> proc_macro2::TokenStream {
+/// Converts `ParamValue` expression to Rust type, produces
+/// `Result<T, ConversionError>` expression
+pub fn expr_from_os_value(
+    expr: &TokenStream,
+    ty: &ParamType,
+    coercion: &CoercionDesc,
+) -> proc_macro2::TokenStream {
+    let platform_name = ty.platform_name();
+    let type_err = quote! {
+        || native_api_1c::native_api_1c_core::interface::ConversionError::unexpected_type(
+            #platform_name,
+            &#expr,
+        )
+    };
+    if let Some(integer_type) = ty.integer_type() {
+        let coerce_fn = if ty.is_wide_unsigned() {
+            quote! { coerce_to_u64 }
+        } else {
+            quote! { coerce_to_i64 }
+        };
+        let integer_name = integer_type.to_string();
+        return quote! {
+            {
+                let _ = "expr_from_os_value: specific case for integers";
+                (#expr)
+                    .#coerce_fn(#coercion)
+                    .ok_or_else(#type_err)
+                    .and_then(|val| {
+                        #integer_type::try_from(val).map_err(|_| {
+                            native_api_1c::native_api_1c_core::interface::ConversionError::new(
+                                format!("value {val} is out of range for {}", #integer_name),
+                            )
+                        })
+                    })
+            }
+        };
+    }
     match ty {
+        ParamType::Bool => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Bool";
+                (#expr).coerce_to_bool(#coercion).ok_or_else(#type_err)
+            }
+        },
+        ParamType::I32 => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Int";
+                (#expr).coerce_to_i32(#coercion).ok_or_else(#type_err)
+            }
+        },
+        ParamType::F64 => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Float";
+                (#expr).coerce_to_f64(#coercion).ok_or_else(#type_err)
+            }
+        },
         ParamType::String => quote! {
             {
                 let _ = "expr_from_os_value: specific case for String";
-                match &#expr {
-                    #ty(val) => {
-                        Ok(native_api_1c::native_api_1c_core::ffi::string_utils::from_os_string(&val))
-                    },
-                    _ => Err(()),
-                }?.clone()
+                (#expr).coerce_to_string(#coercion).ok_or_else(#type_err)
+            }
+        },
+        ParamType::Custom | ParamType::Json => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Custom";
+                native_api_1c::native_api_1c_core::interface::FromParamValue::from_param_value(
+                    &#expr,
+                    #coercion,
+                )
+            }
+        },
+        ParamType::Decimal => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Decimal";
+                <native_api_1c::native_api_1c_core::rust_decimal::Decimal as
+                    native_api_1c::native_api_1c_core::interface::FromParamValue>::from_param_value(
+                    &#expr,
+                    #coercion,
+                )
+            }
+        },
+        ParamType::Any => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Any";
+                Ok::<_, native_api_1c::native_api_1c_core::interface::ConversionError>(#expr.clone())
             }
         },
         ParamType::Blob => quote! {
             {
                 let _ = "expr_from_os_value: specific case for Blob";
                 match &#expr {
-                    #ty(val) => {
-                        Ok(val)
-                    },
-                    _ => Err(()),
-                }?.clone()
+                    #ty(val) => Ok(val.clone()),
+                    _ => Err((#type_err)()),
+                }
             }
         },
         _ => quote! {
             {
                 let _ = "expr_from_os_value: generic case";
-                match #expr {
-                    #ty(val) => {
-                        Ok(val)
-                    },
-                    _ => Err(()),
-                }?.clone()
+                match &#expr {
+                    #ty(val) => Ok(val.clone()),
+                    _ => Err((#type_err)()),
+                }
             }
         },
     }
 }
+
+/// Same as `expr_to_os_value`, but for `Option<T>` expressions, `None` is converted to `Empty`
+pub fn optional_expr_to_os_value(
+    expr: &TokenStream,
+    ty: &ParamType,
+    string_nil: bool,
+) -> proc_macro2::TokenStream {
+    // `Option<T>` of custom type is converted by `IntoParamValue` itself
+    if ty.uses_traits() {
+        return expr_to_os_value(expr, ty, string_nil);
+    }
+    let some_value = expr_to_os_value(&quote! { inner_val }, ty, string_nil);
+    quote! {
+        match &#expr {
+            Some(inner_val) => #some_value,
+            None => native_api_1c::native_api_1c_core::interface::ParamValue::Empty,
+        }
+    }
+}
+
+/// Same as `expr_to_os_value` and `optional_expr_to_os_value`, but produces
+/// `Result<ParamValue, ConversionError>`, as conversion of `Custom` and `Json` types can fail
+pub fn try_expr_to_os_value(
+    expr: &TokenStream,
+    ty: &ParamType,
+    string_nil: bool,
+    optional: bool,
+) -> proc_macro2::TokenStream {
+    if ty.uses_traits() {
+        return quote! {
+            {
+                let _ = "try_expr_to_os_value: specific case for Custom";
+                native_api_1c::native_api_1c_core::interface::IntoParamValue::try_into_param_value(
+                    #expr.clone()
+                )
+            }
+        };
+    }
+    let value = if optional {
+        optional_expr_to_os_value(expr, ty, string_nil)
+    } else {
+        expr_to_os_value(expr, ty, string_nil)
+    };
+    quote! {
+        Ok::<_, native_api_1c::native_api_1c_core::interface::ConversionError>(#value)
+    }
+}
+
+/// Same as `expr_from_os_value`, but produces `Result<Option<T>, ConversionError>`,
+/// where `Empty` is converted to `None`
+pub fn optional_expr_from_os_value(
+    expr: &TokenStream,
+    ty: &ParamType,
+    coercion: &CoercionDesc,
+) -> proc_macro2::TokenStream {
+    // `Option<T>` of custom type is converted by `FromParamValue` itself
+    if ty.uses_traits() {
+        return expr_from_os_value(expr, ty, coercion);
+    }
+    let some_value = expr_from_os_value(&quote! { inner_val }, ty, coercion);
+    quote! {
+        match &#expr {
+            native_api_1c::native_api_1c_core::interface::ParamValue::Empty => Ok(None),
+            inner_val => #some_value.map(Some),
+        }
+    }
+}
+
+/// Returns `T` if type is `Option<T>` or `&mut Option<T>`
+pub fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Reference(reference) => option_inner_type(&reference.elem),
+        syn::Type::Path(path) => {
+            let last_segment = path.path.segments.last()?;
+            if last_segment.ident != "Option" {
+                return None;
+            }
+            let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+                return None;
+            };
+            match args.args.first()? {
+                syn::GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
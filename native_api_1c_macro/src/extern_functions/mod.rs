@@ -1,67 +1,83 @@
 mod parse;
 
-use proc_macro2::{Span, TokenStream};
+use native_api_1c_core::interface::descriptors::names_equal;
+use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use parse::ExternAddInsDesc;
-use syn::{LitByte, LitStr};
 
-static ASCII_LOWER: [char; 50] = [
-    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', // numbers
-    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', // ASCII uppercase
-    'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', // ASCII uppercase
-    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', // ASCII lowercase
-    'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', // ASCII lowercase
-];
+use crate::derive_addin::names::{is_valid_name, NAME_RULES};
+use syn::LitStr;
 
 pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let extern_add_ins = syn::parse_macro_input!(input as ExternAddInsDesc);
 
-    if extern_add_ins.components.len() > ASCII_LOWER.len() {
-        return quote! {
-            compile_error!("Too many components, max is {}", ASCII_LOWER.len());
-        }
-        .into();
+    if let Err(err) = validate_class_names(&extern_add_ins) {
+        return err.to_compile_error().into();
     }
 
     let mut get_class_object_body = TokenStream::new();
-    for (i, add_in_desc) in extern_add_ins.components.iter().enumerate() {
-        let alias = ASCII_LOWER[i];
-        let alias_literal = LitByte::new(alias as u8, Span::call_site());
-        let alias_literal = alias_literal.to_token_stream();
+    let mut push_names = TokenStream::new();
+    for add_in_desc in &extern_add_ins.components {
+        let class_name_literal = &add_in_desc.class_name;
         let init_tkn = &add_in_desc.init_tkn;
+        let cfg_attrs = &add_in_desc.cfg_attrs;
+
+        // factory receives index of the instance, counted for each class from 0
+        let add_in = if add_in_desc.factory {
+            quote! {{
+                static INSTANCES: std::sync::atomic::AtomicUsize =
+                    std::sync::atomic::AtomicUsize::new(0);
+                let index = INSTANCES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                (#init_tkn)(index)
+            }}
+        } else {
+            init_tkn.clone()
+        };
 
         get_class_object_body.extend(quote! {
-            #alias_literal => {
-                let add_in = #init_tkn;
-                native_api_1c::native_api_1c_core::ffi::create_component(component, add_in)
-            },
-        })
+            #(#cfg_attrs)*
+            if class_name == &utf16_lit::utf16_null!(#class_name_literal)[..] {
+                let add_in = #add_in;
+                return native_api_1c::native_api_1c_core::ffi::create_component(component, add_in);
+            }
+        });
+        push_names.extend(quote! {
+            #(#cfg_attrs)*
+            names.push(#class_name_literal);
+        });
     }
     let get_class_object_body = quote! {
-        match *name as u8 {
-            #get_class_object_body
-            _ => 0,
-        }
+        let class_name = native_api_1c::native_api_1c_core::ffi::string_utils::get_str(name);
+        #get_class_object_body
+        0
     };
 
-    let names = ASCII_LOWER[..extern_add_ins.components.len()]
-        .iter()
-        .map(char::to_string)
-        .collect::<Vec<_>>()
-        .join("|");
-    let names_lit = LitStr::new(&names, Span::call_site());
-    let names_lit = names_lit.to_token_stream();
-    let get_class_names_body = quote! { utf16_lit::utf16_null!(#names_lit).as_ptr() };
+    // components can be excluded with `#[cfg(...)]`, so the list is built at runtime
+    let get_class_names_body = quote! {
+        static CLASS_NAMES: std::sync::OnceLock<Vec<u16>> = std::sync::OnceLock::new();
+        CLASS_NAMES
+            .get_or_init(|| {
+                let mut names: Vec<&str> = Vec::new();
+                #push_names
+                names.join("|").encode_utf16().chain(Some(0)).collect()
+            })
+            .as_ptr()
+    };
 
-    let result = quote! {
-        pub static mut PLATFORM_CAPABILITIES: std::sync::atomic::AtomicI32 =
-            std::sync::atomic::AtomicI32::new(-1);
+    let attach_type = &extern_add_ins.attach_type;
 
+    let result = quote! {
         #[allow(non_snake_case)]
         #[no_mangle]
         pub extern "C" fn GetAttachType() -> native_api_1c::native_api_1c_core::ffi::AttachType {
-            native_api_1c::native_api_1c_core::ffi::AttachType::Any
+            #attach_type
+        }
+
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub extern "C" fn SetPlatformCapabilities(capabilities: std::ffi::c_int) -> std::ffi::c_int {
+            native_api_1c::native_api_1c_core::ffi::set_platform_capabilities(capabilities)
         }
 
         #[allow(non_snake_case)]
@@ -88,3 +104,44 @@ pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStre
 
     result.into()
 }
+
+/// Checks that every class name can be used in `AddIn.<Library>.<Class>` from 1C,
+/// and that no two components share the same name (1C names are case-insensitive).
+/// Components with different `#[cfg(...)]` attributes can share the name, e.g. for
+/// different platforms
+fn validate_class_names(extern_add_ins: &ExternAddInsDesc) -> syn::Result<()> {
+    let mut seen_names: Vec<(&LitStr, String)> = vec![];
+    for add_in_desc in &extern_add_ins.components {
+        let class_name = &add_in_desc.class_name;
+        let name = class_name.value();
+
+        if !is_valid_name(&name) {
+            return Err(syn::Error::new(
+                class_name.span(),
+                format!("invalid class name `{name}`: {NAME_RULES}"),
+            ));
+        }
+
+        let cfg = add_in_desc
+            .cfg_attrs
+            .iter()
+            .map(|attr| attr.to_token_stream().to_string())
+            .collect::<String>();
+        // names are compared as member names, as 1C ignores case
+        let conflict = seen_names.iter().find(|(other, other_cfg)| {
+            names_equal(&other.value(), &name)
+                && (cfg.is_empty() || other_cfg.is_empty() || *other_cfg == cfg)
+        });
+        if let Some((first, _)) = conflict {
+            let mut err =
+                syn::Error::new(class_name.span(), format!("duplicate class name `{name}`"));
+            err.combine(syn::Error::new(
+                first.span(),
+                format!("class name `{}` is first used here", first.value()),
+            ));
+            return Err(err);
+        }
+        seen_names.push((class_name, cfg));
+    }
+    Ok(())
+}
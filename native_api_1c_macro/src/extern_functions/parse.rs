@@ -1,6 +1,6 @@
 use darling::FromMeta;
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, Parser},
     punctuated::Punctuated,
@@ -11,11 +11,31 @@ use syn::{
 
 #[derive(Debug)]
 pub struct ExternAddInsDesc {
+    pub attach_type: AttachTypeDesc,
     pub components: Vec<ExternAddInComponentDesc>,
 }
 
 impl Parse for ExternAddInsDesc {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_inner)?;
+        let extern_functions_attrs = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("extern_functions"))
+            .collect::<Vec<_>>();
+        if extern_functions_attrs.len() > 1 {
+            return Err(syn::Error::new(
+                extern_functions_attrs[1].span(),
+                "at most one `extern_functions` attribute is allowed",
+            ));
+        }
+        let options = extern_functions_attrs
+            .first()
+            .map(|attr| {
+                ExternFunctionsMeta::from_meta(&attr.meta)
+                    .map_err(|err| syn::Error::new(attr.span(), err.to_string()))
+            })
+            .transpose()?;
+
         let parser = Punctuated::<syn::Expr, Comma>::parse_terminated(input).unwrap();
 
         let components = parser
@@ -26,20 +46,90 @@ impl Parse for ExternAddInsDesc {
             })
             .collect::<syn::Result<Vec<_>>>()?;
 
-        Ok(ExternAddInsDesc { components })
+        Ok(ExternAddInsDesc {
+            attach_type: options
+                .and_then(|options| options.attach_type)
+                .unwrap_or_default(),
+            components,
+        })
     }
 }
 
+#[derive(FromMeta, Debug)]
+struct ExternFunctionsMeta {
+    attach_type: Option<AttachTypeDesc>,
+}
+
+/// Mirrors `native_api_1c_core::ffi::AttachType`
+#[derive(Debug, Default)]
+pub enum AttachTypeDesc {
+    NotIsolated,
+    Isolated,
+    #[default]
+    Any,
+}
+
+const ATTACH_TYPE_ERR: &str = "expected one of: NotIsolated, Isolated, Any";
+
+impl FromMeta for AttachTypeDesc {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let expr_string = match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(str_lit),
+                ..
+            }) => str_lit.value(),
+            syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                path.path.get_ident().unwrap().to_string()
+            }
+            _ => return Err(darling::Error::custom(ATTACH_TYPE_ERR).with_span(expr)),
+        };
+        Self::from_string(&expr_string).map_err(|err| err.with_span(expr))
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "NotIsolated" => Ok(Self::NotIsolated),
+            "Isolated" => Ok(Self::Isolated),
+            "Any" => Ok(Self::Any),
+            _ => Err(darling::Error::custom(format!(
+                "unknown attach type `{value}`, {ATTACH_TYPE_ERR}"
+            ))),
+        }
+    }
+}
+
+impl ToTokens for AttachTypeDesc {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let variant = match self {
+            AttachTypeDesc::NotIsolated => quote! { NotIsolated },
+            AttachTypeDesc::Isolated => quote! { Isolated },
+            AttachTypeDesc::Any => quote! { Any },
+        };
+        tokens.extend(quote! {
+            native_api_1c::native_api_1c_core::ffi::AttachType::#variant
+        });
+    }
+}
+
+/// Options of the component, set with `#[add_in_component(...)]`
+/// # Fields
+/// * `name_override` - class name of the component
+/// * `factory` - init expression is a function, receiving index of the instance
 #[derive(FromMeta, Debug)]
 struct ExternAddInComponentMeta {
     #[darling(rename = "name")]
-    name_override: Option<String>,
+    name_override: Option<syn::LitStr>,
+    factory: Option<()>,
 }
 
 #[derive(Debug)]
 pub struct ExternAddInComponentDesc {
-    pub name_override: Option<String>,
+    pub class_name: syn::LitStr,
     pub init_tkn: TokenStream,
+    /// `init_tkn` is a closure or function, receiving index of the instance
+    pub factory: bool,
+    /// `#[cfg(...)]` attributes of the component
+    pub cfg_attrs: Vec<Attribute>,
 }
 
 impl Parse for ExternAddInComponentDesc {
@@ -61,18 +151,80 @@ impl Parse for ExternAddInComponentDesc {
             Some(Ok(desc)) => Some(desc),
             Some(Err(err)) => {
                 return Err(syn::Error::new(
-                    add_in_component_attrs[1].span(),
+                    add_in_component_attrs[0].span(),
                     err.to_string(),
                 ))
             }
             None => None,
         };
 
+        let cfg_attrs = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .cloned()
+            .collect();
+
         let init_tkn = input.call(TokenStream::parse).unwrap();
+        let factory = addin_desc
+            .as_ref()
+            .is_some_and(|desc| desc.factory.is_some())
+            || matches!(syn::parse2(init_tkn.clone()), Ok(syn::Expr::Closure(_)));
+
+        let class_name = match addin_desc.and_then(|desc| desc.name_override) {
+            Some(name_override) => name_override,
+            None => {
+                let init_expr = syn::parse2::<syn::Expr>(init_tkn.clone())?;
+                let type_ident = init_type_ident(&init_expr).ok_or_else(|| {
+                    syn::Error::new(
+                        init_expr.span(),
+                        "unable to infer class name from expression, \
+                        set it with `#[add_in_component(name = \"...\")]`",
+                    )
+                })?;
+                syn::LitStr::new(&type_ident.to_string(), type_ident.span())
+            }
+        };
 
         Ok(ExternAddInComponentDesc {
-            name_override: addin_desc.and_then(|desc| desc.name_override),
+            class_name,
             init_tkn,
+            factory,
+            cfg_attrs,
         })
     }
 }
+
+/// Finds identifier of the type, constructed by init expression, i.e.
+/// `MyAddIn` in `MyAddIn::new()`, `MyAddIn { .. }`, `MyAddIn::default().with_x(1)`
+/// or `|index| MyAddIn::new(index)`. Calls of free functions, e.g.
+/// `printer::new_printer()`, name no type, so `None` is returned for them
+fn init_type_ident(expr: &syn::Expr) -> Option<&syn::Ident> {
+    match expr {
+        syn::Expr::Call(call) => match call.func.as_ref() {
+            syn::Expr::Path(path) if path.path.segments.len() > 1 => {
+                let segments = &path.path.segments;
+                let function = &segments[segments.len() - 1].ident;
+                let type_ident = &segments[segments.len() - 2].ident;
+                (starts_uppercase(type_ident) && !starts_uppercase(function)).then_some(type_ident)
+            }
+            _ => None,
+        },
+        syn::Expr::Struct(struct_expr) => struct_expr
+            .path
+            .segments
+            .last()
+            .map(|segment| &segment.ident),
+        syn::Expr::MethodCall(method_call) => init_type_ident(&method_call.receiver),
+        syn::Expr::Paren(paren) => init_type_ident(&paren.expr),
+        syn::Expr::Closure(closure) => init_type_ident(&closure.body),
+        _ => None,
+    }
+}
+
+/// Types are named in UpperCamelCase, functions and modules in snake_case
+fn starts_uppercase(ident: &syn::Ident) -> bool {
+    ident
+        .to_string()
+        .trim_start_matches("r#")
+        .starts_with(|c: char| c.is_uppercase())
+}
@@ -1,11 +1,31 @@
 mod derive_addin;
 mod extern_functions;
 
-#[proc_macro_derive(AddIn, attributes(add_in_prop, add_in_func, add_in_con, arg, returns))]
+#[proc_macro_derive(
+    AddIn,
+    attributes(
+        add_in,
+        add_in_prop,
+        add_in_func,
+        add_in_con,
+        add_in_flatten,
+        add_in_setting,
+        arg,
+        returns
+    )
+)]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_addin::derive(input)
 }
 
+#[proc_macro_attribute]
+pub fn add_in_methods(
+    attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    derive_addin::methods::add_in_methods(attr, input)
+}
+
 #[proc_macro]
 pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     extern_functions::extern_functions(input)
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, provided_types::Tm, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+#[derive(AddIn)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Float, name = "Rate", name_ru = "Ставка", readable, writable)]
+    rate: f64,
+
+    #[add_in_prop(ty = Int, name = "Count", name_ru = "Количество", readable, writable, strict)]
+    count: i32,
+
+    #[add_in_func(name = "Float", name_ru = "Дробное")]
+    #[arg(ty = Float)]
+    #[returns(ty = Float)]
+    pub float: fn(f64) -> f64,
+
+    #[add_in_func(name = "StrictFloat", name_ru = "СтрогоеДробное")]
+    #[arg(ty = Float, strict)]
+    #[returns(ty = Float)]
+    pub strict_float: fn(f64) -> f64,
+
+    #[add_in_func(name = "Int", name_ru = "Целое")]
+    #[arg(ty = Int)]
+    #[returns(ty = Int)]
+    pub int: fn(i32) -> i32,
+
+    #[add_in_func(name = "Bool", name_ru = "Булево")]
+    #[arg(ty = Bool, coerce_bool)]
+    #[arg(ty = Int, coerce_bool)]
+    #[returns(ty = Int)]
+    pub bool: fn(bool, i32) -> i32,
+
+    #[add_in_func(name = "Str", name_ru = "Строка")]
+    #[arg(ty = Str, coerce_string)]
+    #[arg(ty = Str)]
+    #[returns(ty = Str)]
+    pub str: fn(String, String) -> String,
+
+    #[add_in_func(name = "Wide", name_ru = "Широкое")]
+    #[arg(ty = Int64, strict)]
+    #[arg(ty = UInt32, coerce_bool)]
+    #[returns(ty = Int64)]
+    pub wide: fn(i64, u32) -> i64,
+
+    #[add_in_func(name = "Id", name_ru = "Идентификатор")]
+    #[arg(ty = Custom, strict)]
+    #[returns(ty = Int64)]
+    pub id: fn(u64) -> i64,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        rate: 0.0,
+        count: 0,
+        float: |a| a,
+        strict_float: |a| a,
+        int: |a| a,
+        bool: |a, b| i32::from(a) + b,
+        str: |a, b| a + &b,
+        wide: |a, b| a + i64::from(b),
+        id: |a| a as i64,
+    }
+}
+
+fn call(add_in: &mut TestAddIn, method: usize, params: Vec<ParamValue>) -> Result<ParamValue, ()> {
+    let mut params = ParamValues::new(params);
+    add_in.call_as_func(method, &mut params)
+}
+
+#[rstest]
+#[case(ParamValue::F64(1.5), Ok(ParamValue::F64(1.5)))]
+#[case(ParamValue::I32(5), Ok(ParamValue::F64(5.0)))]
+#[case(ParamValue::I64(1 << 60), Ok(ParamValue::F64((1i64 << 60) as f64)))]
+#[case(ParamValue::Bool(true), Err(()))]
+#[case(ParamValue::String(os_string("5")), Err(()))]
+fn test_float(
+    mut add_in: TestAddIn,
+    #[case] arg: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    assert_eq!(call(&mut add_in, 0, vec![arg]), expected);
+}
+
+#[rstest]
+#[case(ParamValue::F64(1.5), Ok(ParamValue::F64(1.5)))]
+#[case(ParamValue::I32(5), Err(()))]
+fn test_strict_float(
+    mut add_in: TestAddIn,
+    #[case] arg: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    assert_eq!(call(&mut add_in, 1, vec![arg]), expected);
+}
+
+#[rstest]
+#[case(ParamValue::I32(5), Ok(ParamValue::I32(5)))]
+#[case(ParamValue::F64(5.0), Ok(ParamValue::I32(5)))]
+#[case(ParamValue::F64(5.5), Err(()))]
+#[case(ParamValue::F64(1e10), Err(()))]
+#[case(ParamValue::Bool(true), Err(()))]
+fn test_int(
+    mut add_in: TestAddIn,
+    #[case] arg: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    assert_eq!(call(&mut add_in, 2, vec![arg]), expected);
+}
+
+#[rstest]
+#[case(ParamValue::Bool(true), ParamValue::Bool(true), Ok(ParamValue::I32(2)))]
+#[case(ParamValue::I32(7), ParamValue::I32(7), Ok(ParamValue::I32(8)))]
+#[case(ParamValue::I32(0), ParamValue::F64(2.0), Ok(ParamValue::I32(2)))]
+#[case(ParamValue::F64(1.0), ParamValue::I32(0), Err(()))]
+fn test_bool(
+    mut add_in: TestAddIn,
+    #[case] a: ParamValue,
+    #[case] b: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    assert_eq!(call(&mut add_in, 3, vec![a, b]), expected);
+}
+
+#[rstest]
+#[case(ParamValue::I32(42), Ok("42"))]
+#[case(ParamValue::F64(1.5), Ok("1.5"))]
+#[case(ParamValue::F64(2.0), Ok("2"))]
+#[case(ParamValue::Date(Tm { year: 124, mon: 0, mday: 31, hour: 12, ..Default::default() }), Ok("2024-01-31T12:00:00"))]
+#[case(ParamValue::Bool(true), Err(()))]
+fn test_str(mut add_in: TestAddIn, #[case] arg: ParamValue, #[case] expected: Result<&str, ()>) {
+    assert_eq!(
+        call(&mut add_in, 4, vec![arg, ParamValue::String(os_string(""))]),
+        expected.map(|expected| ParamValue::String(os_string(expected)))
+    );
+}
+
+#[rstest]
+fn test_str_not_coerced(mut add_in: TestAddIn) {
+    let result = call(
+        &mut add_in,
+        4,
+        vec![ParamValue::String(os_string("a")), ParamValue::I32(1)],
+    );
+    assert_eq!(result, Err(()));
+}
+
+#[rstest]
+#[case(ParamValue::I32(5), ParamValue::I32(1), Ok(ParamValue::I32(6)))]
+#[case(ParamValue::I64(1 << 40), ParamValue::Bool(true), Ok(ParamValue::F64(((1i64 << 40) + 1) as f64)))]
+#[case(ParamValue::F64(5.0), ParamValue::I32(1), Err(()))]
+#[case(ParamValue::I32(5), ParamValue::F64(1.0), Ok(ParamValue::I32(6)))]
+#[case(ParamValue::Bool(true), ParamValue::I32(1), Err(()))]
+fn test_wide(
+    mut add_in: TestAddIn,
+    #[case] a: ParamValue,
+    #[case] b: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    assert_eq!(call(&mut add_in, 5, vec![a, b]), expected);
+}
+
+#[rstest]
+#[case(ParamValue::I32(5), Ok(ParamValue::I32(5)))]
+#[case(ParamValue::F64(5.0), Err(()))]
+fn test_custom_strict(
+    mut add_in: TestAddIn,
+    #[case] arg: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    assert_eq!(call(&mut add_in, 6, vec![arg]), expected);
+}
+
+#[rstest]
+fn test_set_prop_val(mut add_in: TestAddIn) {
+    assert_eq!(add_in.set_prop_val(0, ParamValue::I32(3)), Ok(()));
+    assert_eq!(add_in.rate, 3.0);
+
+    assert_eq!(add_in.set_prop_val(1, ParamValue::F64(3.0)), Err(()));
+    assert_eq!(add_in.set_prop_val(1, ParamValue::I32(3)), Ok(()));
+    assert_eq!(add_in.count, 3);
+}
@@ -0,0 +1,211 @@
+//! Fake 1C platform, that records calls of `Connection` methods
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    ffi::{c_long, c_ushort},
+    sync::{Mutex, MutexGuard},
+};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        provided_types::{DataStr, TVariant, VariantType},
+        string_utils::{from_os_string, get_str},
+    },
+    interface::{ConnectionField, ConnectionHandle, ParamValue},
+};
+use rstest::fixture;
+
+/// Error, reported with `Connection::add_error`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportedError {
+    pub code: u16,
+    pub source: String,
+    pub description: String,
+}
+
+/// Everything the AddIn has passed to the platform
+#[derive(Default)]
+pub struct PlatformState {
+    pub errors: Vec<ReportedError>,
+    /// Profile, registered last, values are read and written there
+    pub profile_name: Option<String>,
+    pub profiles: HashMap<String, HashMap<String, ParamValue>>,
+    /// `None` stands for reset of the status line
+    pub status_lines: Vec<Option<String>>,
+    /// `register_profile_as` returns `false`, if set
+    pub profile_unavailable: bool,
+}
+
+impl PlatformState {
+    /// Values, stored in the profile `name`
+    pub fn profile(&mut self, name: &str) -> &mut HashMap<String, ParamValue> {
+        self.profiles.entry(name.to_string()).or_default()
+    }
+
+    /// Values of the profile, registered last
+    fn current_profile(&mut self) -> Option<&mut HashMap<String, ParamValue>> {
+        let name = self.profile_name.clone()?;
+        Some(self.profile(&name))
+    }
+}
+
+/// Same layout as `ConnectionVTable`, event functions are not implemented
+#[repr(C)]
+struct FakeConnectionVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    add_error:
+        unsafe extern "system" fn(&FakePlatform, c_ushort, *const u16, *const u16, c_long) -> bool,
+    read: unsafe extern "system" fn(
+        &FakePlatform,
+        *mut u16,
+        &mut TVariant,
+        *mut c_long,
+        *mut *mut u16,
+    ) -> bool,
+    write: unsafe extern "system" fn(&FakePlatform, *mut u16, &mut TVariant) -> bool,
+    register_profile_as: unsafe extern "system" fn(&FakePlatform, *mut u16) -> bool,
+    set_event_buffer_depth: usize,
+    get_event_buffer_depth: usize,
+    external_event: usize,
+    clean_event_buffer: usize,
+    set_status_line: unsafe extern "system" fn(&FakePlatform, *mut u16) -> bool,
+    reset_status_line: unsafe extern "system" fn(&FakePlatform),
+}
+
+/// Object, passed to the AddIn as `Connection`
+#[repr(C)]
+pub struct FakePlatform {
+    vptr: &'static FakeConnectionVTable,
+    state: Mutex<PlatformState>,
+}
+
+impl FakePlatform {
+    /// Creates platform, that lives until the end of the test, as the
+    /// connection is `'static`
+    pub fn new() -> &'static Self {
+        Box::leak(Box::new(Self {
+            vptr: &FAKE_VTABLE,
+            state: Mutex::new(PlatformState::default()),
+        }))
+    }
+
+    pub fn connection(&'static self) -> &'static Connection {
+        unsafe { &*(self as *const Self as *const Connection) }
+    }
+
+    /// Handle, that already has the connection
+    pub fn handle(&'static self) -> ConnectionHandle {
+        let mut handle = ConnectionHandle::new();
+        handle.set_connection(self.connection());
+        handle
+    }
+
+    pub fn state(&self) -> MutexGuard<'_, PlatformState> {
+        self.state.lock().unwrap()
+    }
+}
+
+#[fixture]
+pub fn platform() -> &'static FakePlatform {
+    FakePlatform::new()
+}
+
+unsafe extern "system" fn add_error(
+    platform: &FakePlatform,
+    code: c_ushort,
+    source: *const u16,
+    description: *const u16,
+    _: c_long,
+) -> bool {
+    platform.state().errors.push(ReportedError {
+        code,
+        source: from_os_string(get_str(source)),
+        description: from_os_string(get_str(description)),
+    });
+    true
+}
+
+unsafe extern "system" fn read(
+    platform: &FakePlatform,
+    name: *mut u16,
+    value: &mut TVariant,
+    _: *mut c_long,
+    _: *mut *mut u16,
+) -> bool {
+    let name = from_os_string(get_str(name));
+    let stored = platform
+        .state()
+        .current_profile()
+        .and_then(|profile| profile.get(&name).cloned());
+    let Some(stored) = stored else {
+        return false;
+    };
+    match stored {
+        ParamValue::I32(v) => value.update_to_i32(v),
+        ParamValue::Bool(v) => value.update_to_bool(v),
+        ParamValue::String(v) => {
+            let v = v.leak();
+            value.value.data_str = DataStr {
+                ptr: v.as_mut_ptr(),
+                len: v.len() as u32,
+            };
+            value.vt = VariantType::WStr;
+        }
+        _ => unimplemented!(),
+    }
+    true
+}
+
+unsafe extern "system" fn write(
+    platform: &FakePlatform,
+    name: *mut u16,
+    value: &mut TVariant,
+) -> bool {
+    let name = from_os_string(get_str(name));
+    let value = ParamValue::from(&*value);
+    let mut state = platform.state();
+    let Some(profile) = state.current_profile() else {
+        return false;
+    };
+    profile.insert(name, value);
+    true
+}
+
+unsafe extern "system" fn register_profile_as(platform: &FakePlatform, name: *mut u16) -> bool {
+    let mut state = platform.state();
+    if state.profile_unavailable {
+        return false;
+    }
+    state.profile_name = Some(from_os_string(get_str(name)));
+    true
+}
+
+unsafe extern "system" fn set_status_line(platform: &FakePlatform, text: *mut u16) -> bool {
+    let text = from_os_string(get_str(text));
+    platform.state().status_lines.push(Some(text));
+    true
+}
+
+unsafe extern "system" fn reset_status_line(platform: &FakePlatform) {
+    platform.state().status_lines.push(None);
+}
+
+static FAKE_VTABLE: FakeConnectionVTable = FakeConnectionVTable {
+    dtor: 0,
+    #[cfg(target_family = "unix")]
+    dtor2: 0,
+    add_error,
+    read,
+    write,
+    register_profile_as,
+    set_event_buffer_depth: 0,
+    get_event_buffer_depth: 0,
+    external_event: 0,
+    clean_event_buffer: 0,
+    set_status_line,
+    reset_status_line,
+};
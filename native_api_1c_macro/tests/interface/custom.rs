@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{
+        AddInWrapper, Coercion, ConversionError, FromParamValue, IntoParamValue, ParamValue,
+        ParamValues,
+    },
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Color {
+    Red,
+    Green,
+}
+
+impl FromParamValue for Color {
+    fn from_param_value(value: &ParamValue, coercion: Coercion) -> Result<Self, ConversionError> {
+        match String::from_param_value(value, coercion)?.as_str() {
+            "red" => Ok(Color::Red),
+            "green" => Ok(Color::Green),
+            other => Err(ConversionError::new(format!("unknown color {other}"))),
+        }
+    }
+}
+
+impl IntoParamValue for Color {
+    fn into_param_value(self) -> ParamValue {
+        match self {
+            Color::Red => "red",
+            Color::Green => "green",
+        }
+        .into_param_value()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct OrderId(u32);
+
+impl FromParamValue for OrderId {
+    fn from_param_value(value: &ParamValue, coercion: Coercion) -> Result<Self, ConversionError> {
+        u32::from_param_value(value, coercion).map(OrderId)
+    }
+}
+
+impl IntoParamValue for OrderId {
+    fn into_param_value(self) -> ParamValue {
+        self.0.into_param_value()
+    }
+}
+
+#[derive(AddIn)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Custom, name = "Color", name_ru = "Цвет", readable, writable)]
+    color: Color,
+
+    #[add_in_func(name = "NextOrder", name_ru = "СледующийЗаказ")]
+    #[arg(ty = Custom)]
+    #[arg(ty = Custom, default = "green")]
+    #[returns(ty = Custom, result)]
+    pub next_order: fn(&Self, OrderId, Color) -> Result<OrderId, ()>,
+
+    #[add_in_func(name = "FindOrder", name_ru = "НайтиЗаказ")]
+    #[arg(ty = Custom)]
+    #[returns(ty = Custom)]
+    pub find_order: fn(Option<OrderId>) -> Option<OrderId>,
+
+    #[add_in_func(name = "SwapColor", name_ru = "ПоменятьЦвет")]
+    #[arg(ty = Custom, as_out)]
+    pub swap_color: fn(&mut Self, &mut Color),
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        color: Color::Red,
+        next_order: |addin, id, color| {
+            if color == addin.color {
+                Ok(OrderId(id.0 + 1))
+            } else {
+                Err(())
+            }
+        },
+        find_order: |id| id.filter(|id| id.0 > 0),
+        swap_color: |addin, color| std::mem::swap(&mut addin.color, color),
+    }
+}
+
+fn color(value: &str) -> ParamValue {
+    ParamValue::String(os_string(value))
+}
+
+#[rstest]
+fn test_get_prop_val(add_in: TestAddIn) {
+    assert_eq!(add_in.get_prop_val(0), Ok(color("red")));
+}
+
+#[rstest]
+#[case(color("green"), Ok(Color::Green))]
+#[case(color("blue"), Err(()))]
+#[case(ParamValue::I32(1), Err(()))]
+fn test_set_prop_val(
+    mut add_in: TestAddIn,
+    #[case] value: ParamValue,
+    #[case] expected: Result<Color, ()>,
+) {
+    let result = add_in.set_prop_val(0, value);
+    assert_eq!(result.map(|_| add_in.color), expected);
+}
+
+#[rstest]
+fn test_get_param_def_value(add_in: TestAddIn) {
+    assert_eq!(add_in.get_param_def_value(0, 1), Some(color("green")));
+}
+
+#[rstest]
+#[case(ParamValue::I32(41), color("red"), Ok(ParamValue::I32(42)))]
+#[case(ParamValue::F64(41.0), color("red"), Ok(ParamValue::I32(42)))]
+#[case(ParamValue::I32(41), color("green"), Err(()))]
+#[case(ParamValue::I32(-1), color("red"), Err(()))]
+fn test_call_next_order(
+    mut add_in: TestAddIn,
+    #[case] id: ParamValue,
+    #[case] color: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    let mut params = ParamValues::new(vec![id, color]);
+    assert_eq!(add_in.call_as_func(0, &mut params), expected);
+}
+
+#[rstest]
+#[case(ParamValue::I32(7), ParamValue::I32(7))]
+#[case(ParamValue::I32(0), ParamValue::Empty)]
+#[case(ParamValue::Empty, ParamValue::Empty)]
+fn test_call_find_order(
+    mut add_in: TestAddIn,
+    #[case] id: ParamValue,
+    #[case] expected: ParamValue,
+) {
+    let mut params = ParamValues::new(vec![id]);
+    assert_eq!(add_in.call_as_func(1, &mut params), Ok(expected));
+}
+
+#[rstest]
+fn test_call_swap_color(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![color("green")]);
+    assert_eq!(add_in.call_as_proc(2, &mut params), Ok(()));
+    assert_eq!(params[0], color("red"));
+    assert_eq!(add_in.color, Color::Green);
+}
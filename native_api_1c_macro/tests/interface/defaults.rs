@@ -0,0 +1,67 @@
+use chrono::NaiveDateTime;
+use native_api_1c::native_api_1c_core::interface::{AddInWrapper, ParamValue, ParamValues};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+#[derive(AddIn)]
+struct TestAddIn {
+    last_call: Option<(NaiveDateTime, NaiveDateTime, Vec<u8>, Vec<u8>)>,
+
+    #[add_in_func(name = "Export", name_ru = "Выгрузить")]
+    #[arg(ty = Date, default = "2024-02-29T13:45:30")]
+    #[arg(ty = Date, default = "")]
+    #[arg(ty = Blob, default = b"\x01\x02")]
+    #[arg(ty = Blob, default = [])]
+    pub export: fn(&mut Self, NaiveDateTime, NaiveDateTime, Vec<u8>, Vec<u8>),
+
+    #[add_in_func(name = "Since", name_ru = "С")]
+    #[arg(ty = Date, default = "1999-12-31")]
+    #[returns(ty = Date)]
+    pub since: fn(NaiveDateTime) -> NaiveDateTime,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        last_call: None,
+        export: |addin, from, to, header, footer| {
+            addin.last_call = Some((from, to, header, footer));
+        },
+        since: |date| date,
+    }
+}
+
+fn date(value: &str) -> ParamValue {
+    let date = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap();
+    ParamValue::Date(date.into())
+}
+
+#[rstest]
+#[case(0, 0, date("2024-02-29T13:45:30"))]
+#[case(0, 1, date("0001-01-01T00:00:00"))]
+#[case(0, 2, ParamValue::Blob(vec![1, 2]))]
+#[case(0, 3, ParamValue::Blob(vec![]))]
+#[case(1, 0, date("1999-12-31T00:00:00"))]
+fn test_get_param_def_value(
+    add_in: TestAddIn,
+    #[case] method_i: usize,
+    #[case] param_i: usize,
+    #[case] expected: ParamValue,
+) {
+    assert_eq!(
+        add_in.get_param_def_value(method_i, param_i),
+        Some(expected)
+    );
+}
+
+#[rstest]
+fn test_call_with_defaults(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_proc(0, &mut params), Ok(()));
+
+    let (from, to, header, footer) = add_in.last_call.unwrap();
+    assert_eq!(from.to_string(), "2024-02-29 13:45:30");
+    assert_eq!(to.to_string(), "0001-01-01 00:00:00");
+    assert_eq!(header, vec![1, 2]);
+    assert!(footer.is_empty());
+}
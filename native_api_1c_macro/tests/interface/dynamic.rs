@@ -0,0 +1,163 @@
+mod common;
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use common::{platform, FakePlatform};
+use native_api_1c::native_api_1c_core::{
+    ffi::string_utils::{os_string, os_string_nil},
+    interface::{
+        descriptors::MethodError, AddInWrapper, DynamicAddIn, DynamicMethod, ParamValue,
+        ParamValues,
+    },
+};
+use rstest::{fixture, rstest};
+use utf16_lit::utf16_null;
+
+/// Queries, as if read from a configuration file
+const QUERIES: &[&str] = &["CountOrders", "CountClients"];
+
+#[fixture]
+fn add_in() -> DynamicAddIn {
+    let limit = Rc::new(Cell::new(10));
+    let log = Rc::new(RefCell::new(vec![]));
+
+    let limit_get = limit.clone();
+    let log_write = log.clone();
+    DynamicAddIn::new("Queries")
+        .property("Version", "Версия", || Ok(ParamValue::I32(1)))
+        .writable_property(
+            "Limit",
+            "Лимит",
+            move || Ok(ParamValue::I32(limit_get.get())),
+            move |val| match val {
+                ParamValue::I32(val) => {
+                    limit.set(val);
+                    Ok(())
+                }
+                _ => Err("expected Int".to_string()),
+            },
+        )
+        .method(
+            "Add",
+            "Сложить",
+            vec![None, Some(ParamValue::I32(1))],
+            |params| match (&params[0], &params[1]) {
+                (ParamValue::I32(a), ParamValue::I32(b)) => Ok(ParamValue::I32(a + b)),
+                _ => Err(MethodError::Failed),
+            },
+        )
+        .procedure("Log", "Записать", vec![None], move |params| {
+            log_write.borrow_mut().push(params[0].clone());
+            Ok(())
+        })
+        .fallback(|name| {
+            let index = QUERIES.iter().position(|query| *query == name)?;
+            Some(DynamicMethod::function(vec![], move |_| {
+                Ok(ParamValue::I32(index as i32))
+            }))
+        })
+}
+
+#[rstest]
+fn test_register_extension_as(mut add_in: DynamicAddIn) {
+    assert_eq!(add_in.register_extension_as(), &utf16_null!("Queries"));
+}
+
+#[rstest]
+fn test_props(mut add_in: DynamicAddIn) {
+    assert_eq!(add_in.get_n_props(), 2);
+    assert_eq!(add_in.find_prop(&os_string_nil("Лимит")), Some(1));
+    assert_eq!(add_in.find_prop(&os_string_nil("ЛИМИТ")), Some(1));
+    assert_eq!(add_in.find_prop(&os_string_nil("Unknown")), None);
+    assert_eq!(add_in.get_prop_name(0, 1), Some(os_string_nil("Версия")));
+
+    assert!(add_in.is_prop_readable(0));
+    assert!(!add_in.is_prop_writable(0));
+    assert!(add_in.is_prop_writable(1));
+
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::I32(1)));
+    assert_eq!(add_in.set_prop_val(0, ParamValue::I32(2)), Err(()));
+    assert_eq!(add_in.set_prop_val(1, ParamValue::I32(20)), Ok(()));
+    assert_eq!(add_in.get_prop_val(1), Ok(ParamValue::I32(20)));
+}
+
+#[rstest]
+fn test_prop_error(mut add_in: DynamicAddIn, platform: &'static FakePlatform) {
+    add_in.init(platform.connection());
+    let mut add_in = add_in.property("Status", "Статус", || {
+        Err("device is offline".to_string())
+    });
+
+    assert_eq!(add_in.get_prop_val(2), Err(()));
+    assert_eq!(add_in.set_prop_val(1, ParamValue::Bool(true)), Err(()));
+    let errors = &platform.state().errors;
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].source, "Queries");
+    assert_eq!(errors[0].description, "Status: device is offline");
+    assert_eq!(errors[1].description, "Limit: expected Int");
+}
+
+#[rstest]
+fn test_methods(mut add_in: DynamicAddIn) {
+    assert_eq!(add_in.get_n_methods(), 2);
+    assert_eq!(add_in.find_method(&os_string_nil("Сложить")), Some(0));
+    assert_eq!(add_in.find_method(&os_string_nil("сложить")), Some(0));
+    assert_eq!(add_in.get_method_name(1, 0), Some(os_string_nil("Log")));
+    assert_eq!(add_in.get_n_params(0), 2);
+    assert_eq!(add_in.get_param_def_value(0, 0), None);
+    assert_eq!(add_in.get_param_def_value(0, 1), Some(ParamValue::I32(1)));
+    assert!(add_in.has_ret_val(0));
+    assert!(!add_in.has_ret_val(1));
+
+    let mut params = ParamValues::new(vec![ParamValue::I32(2)]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Ok(ParamValue::I32(3)));
+
+    let mut params = ParamValues::new(vec![ParamValue::String(os_string("done"))]);
+    assert_eq!(add_in.call_as_proc(1, &mut params), Ok(()));
+    assert_eq!(add_in.call_as_func(1, &mut params), Err(()));
+
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Err(()));
+}
+
+#[rstest]
+fn test_fallback(mut add_in: DynamicAddIn) {
+    assert_eq!(add_in.find_method(&os_string_nil("CountClients")), Some(2));
+    assert_eq!(add_in.find_method(&os_string_nil("CountOrders")), Some(3));
+    // resolved methods keep their numbers
+    assert_eq!(add_in.find_method(&os_string_nil("CountClients")), Some(2));
+    assert_eq!(add_in.find_method(&os_string_nil("DropTables")), None);
+    assert_eq!(add_in.get_n_methods(), 4);
+    assert_eq!(
+        add_in.get_method_name(2, 1),
+        Some(os_string_nil("CountClients"))
+    );
+
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_func(2, &mut params), Ok(ParamValue::I32(1)));
+    assert_eq!(add_in.call_as_func(3, &mut params), Ok(ParamValue::I32(0)));
+}
+
+#[rstest]
+fn test_fallback_same_name(add_in: DynamicAddIn) {
+    let first = add_in.find_method(&os_string_nil("CountOrders"));
+    assert_eq!(first, Some(2));
+    assert_eq!(add_in.find_method(&os_string_nil("CountOrders")), first);
+    assert_eq!(add_in.find_method(&os_string_nil("COUNTORDERS")), first);
+    assert_eq!(add_in.get_n_methods(), 3);
+
+    // unknown names are not added
+    assert_eq!(add_in.find_method(&os_string_nil("DropTables")), None);
+    assert_eq!(add_in.find_method(&os_string_nil("DropTables")), None);
+    assert_eq!(add_in.get_n_methods(), 3);
+}
+
+#[rstest]
+fn test_without_fallback() {
+    let add_in = DynamicAddIn::new("Empty");
+    assert_eq!(add_in.find_method(&os_string_nil("Anything")), None);
+    assert_eq!(add_in.get_n_methods(), 0);
+}
@@ -0,0 +1,172 @@
+use native_api_1c::native_api_1c_core::{
+    ffi::{provided_types::Tm, string_utils::os_string},
+    interface::{
+        descriptors::{AddInDescriptor, MethodError},
+        AddInWrapper, ConversionError, ParamValue, ParamValues,
+    },
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+#[derive(AddIn)]
+struct TestAddIn {
+    #[add_in_func(name = "Transfer", name_ru = "Перевести")]
+    #[arg(ty = Str, name = "Account")]
+    #[arg(ty = Float, name = "Amount")]
+    #[returns(ty = Bool, result)]
+    pub transfer: fn(String, f64) -> Result<bool, String>,
+
+    #[add_in_func(name = "SetLevel", name_ru = "УстановитьУровень")]
+    pub set_level: fn(u8, Option<Tm>),
+
+    #[add_in_func(name = "Round", name_ru = "Округлить")]
+    #[arg(ty = Float)]
+    #[arg(ty = Int, default = 2)]
+    #[returns(ty = Float)]
+    pub round: fn(f64, i32) -> f64,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        transfer: |account, amount| {
+            if account.is_empty() {
+                return Err("no account".to_string());
+            }
+            Ok(amount > 0.0)
+        },
+        set_level: |_, _| {},
+        round: |value, digits| {
+            let factor = 10f64.powi(digits);
+            (value * factor).round() / factor
+        },
+    }
+}
+
+fn string(value: &str) -> ParamValue {
+    ParamValue::String(os_string(value))
+}
+
+fn call(add_in: &mut TestAddIn, method: usize, params: Vec<ParamValue>) -> Result<(), MethodError> {
+    let mut params = ParamValues::new(params);
+    (TestAddIn::METHODS[method].call)(add_in, &mut params).map(|_| ())
+}
+
+#[rstest]
+#[case(
+    vec![string("40817"), string("100")],
+    "argument 2 (Amount) expected Number, got String"
+)]
+#[case(
+    vec![ParamValue::I32(40817), ParamValue::F64(100.0)],
+    "argument 1 (Account) expected String, got Number"
+)]
+fn test_named_argument(
+    mut add_in: TestAddIn,
+    #[case] params: Vec<ParamValue>,
+    #[case] expected: &str,
+) {
+    let err = call(&mut add_in, 0, params).unwrap_err();
+    assert_eq!(err.to_string(), expected);
+}
+
+#[rstest]
+#[case(vec![string("1"), ParamValue::Empty], "argument 1 expected Number, got String")]
+#[case(vec![ParamValue::I32(300), ParamValue::Empty], "argument 1 value 300 is out of range for u8")]
+#[case(
+    vec![ParamValue::I32(1), ParamValue::Bool(true)],
+    "argument 2 expected Date, got Boolean"
+)]
+fn test_unnamed_argument(
+    mut add_in: TestAddIn,
+    #[case] params: Vec<ParamValue>,
+    #[case] expected: &str,
+) {
+    let err = call(&mut add_in, 1, params).unwrap_err();
+    assert_eq!(err.to_string(), expected);
+}
+
+#[rstest]
+fn test_argument_error(mut add_in: TestAddIn) {
+    assert_eq!(
+        call(
+            &mut add_in,
+            0,
+            vec![string("40817"), ParamValue::Bool(true)]
+        ),
+        Err(MethodError::argument(
+            1,
+            Some("Amount"),
+            ConversionError::new("expected Number, got Boolean")
+        ))
+    );
+}
+
+#[rstest]
+fn test_method_failed(mut add_in: TestAddIn) {
+    assert_eq!(
+        call(&mut add_in, 0, vec![string(""), ParamValue::F64(1.0)]),
+        Err(MethodError::Failed)
+    );
+}
+
+#[rstest]
+fn test_call_without_connection(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![string("40817"), string("100")]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Err(()));
+
+    let mut params = ParamValues::new(vec![string("40817"), ParamValue::F64(100.0)]);
+    assert_eq!(
+        add_in.call_as_func(0, &mut params),
+        Ok(ParamValue::Bool(true))
+    );
+}
+
+#[rstest]
+fn test_missing_default_argument(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::F64(1.2345)]);
+    assert_eq!(
+        add_in.call_as_func(2, &mut params),
+        Ok(ParamValue::F64(1.23))
+    );
+}
+
+#[rstest]
+fn test_missing_optional_argument(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(1)]);
+    assert_eq!(add_in.call_as_proc(1, &mut params), Ok(()));
+}
+
+#[rstest]
+#[case(vec![])]
+#[case(vec![string("40817")])]
+fn test_missing_argument(mut add_in: TestAddIn, #[case] params: Vec<ParamValue>) {
+    let mut param_values = ParamValues::new(params.clone());
+    assert_eq!(add_in.call_as_func(0, &mut param_values), Err(()));
+    assert!(param_values.get(1).is_none());
+
+    // descriptor called directly reports the first missing argument instead of panicking
+    assert_eq!(
+        call(&mut add_in, 0, params.clone()),
+        Err(MethodError::MissingArgument {
+            index: params.len()
+        })
+    );
+}
+
+#[rstest]
+fn test_extra_argument(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![
+        ParamValue::F64(1.0),
+        ParamValue::I32(1),
+        ParamValue::I32(1),
+    ]);
+    assert_eq!(add_in.call_as_func(2, &mut params), Err(()));
+}
+
+#[rstest]
+#[case(MethodError::ArgumentCount { expected: 2, got: 3 }, "expected 2 arguments, got 3")]
+#[case(MethodError::MissingArgument { index: 0 }, "argument 1 is missing")]
+fn test_count_error_message(#[case] err: MethodError, #[case] expected: &str) {
+    assert_eq!(err.to_string(), expected);
+}
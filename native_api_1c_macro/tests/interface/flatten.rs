@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        string_utils::{os_string, os_string_nil},
+    },
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+#[derive(AddIn)]
+#[add_in(on_done = Self::flush, on_locale = Self::relocalize)]
+struct Logger {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = "Level", name_ru = "Уровень", readable, writable)]
+    level: i32,
+
+    #[add_in_func(name = "Write", name_ru = "Записать")]
+    #[arg(ty = Str)]
+    #[arg(ty = Int, default = 1)]
+    #[returns(ty = Int)]
+    write: fn(&mut Self, String, i32) -> i32,
+
+    lines: Vec<String>,
+    flushed: bool,
+    locale: String,
+}
+
+impl Logger {
+    fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            level: 0,
+            write: |logger, line, times| {
+                for _ in 0..times {
+                    logger.lines.push(line.clone());
+                }
+                logger.lines.len() as i32
+            },
+            lines: vec![],
+            flushed: false,
+            locale: String::new(),
+        }
+    }
+
+    fn flush(&mut self) {
+        self.flushed = true;
+    }
+
+    fn relocalize(&mut self, locale: &str) {
+        self.locale = locale.to_string();
+    }
+}
+
+#[derive(AddIn)]
+struct Jobs {
+    #[add_in_prop(ty = Int, name = "Running", name_ru = "Выполняется", readable)]
+    running: i32,
+
+    #[add_in_func(name = "Start", name_ru = "Запустить")]
+    start: fn(&mut Self),
+}
+
+#[derive(AddIn)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Str, name = "Name", name_ru = "Имя", readable)]
+    name: String,
+
+    #[add_in_func(name = "Ping", name_ru = "Пинг")]
+    #[returns(ty = Str)]
+    ping: fn(&Self) -> String,
+
+    #[add_in_flatten(prefix = "Log", prefix_ru = "Журнал")]
+    logger: Logger,
+
+    #[add_in_flatten]
+    jobs: Jobs,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        name: "Main".to_string(),
+        ping: |_| "pong".to_string(),
+        logger: Logger::new(),
+        jobs: Jobs {
+            running: 0,
+            start: |jobs| jobs.running += 1,
+        },
+    }
+}
+
+#[rstest]
+fn test_counts(add_in: TestAddIn) {
+    assert_eq!(add_in.get_n_props(), 3);
+    assert_eq!(add_in.get_n_methods(), 3);
+}
+
+#[rstest]
+#[case("Name", Some(0))]
+#[case("LogLevel", Some(1))]
+#[case("ЖурналУровень", Some(1))]
+#[case("Выполняется", Some(2))]
+// 1C language ignores case
+#[case("name", Some(0))]
+#[case("LOGLEVEL", Some(1))]
+#[case("журналуровень", Some(1))]
+#[case("Level", None)]
+#[case("LogRunning", None)]
+fn test_find_prop(add_in: TestAddIn, #[case] name: &str, #[case] expected: Option<usize>) {
+    assert_eq!(add_in.find_prop(&os_string_nil(name)), expected);
+}
+
+#[rstest]
+#[case("Ping", Some(0))]
+#[case("LogWrite", Some(1))]
+#[case("ЖурналЗаписать", Some(1))]
+#[case("Start", Some(2))]
+#[case("ЖУРНАЛЗАПИСАТЬ", Some(1))]
+#[case("Write", None)]
+fn test_find_method(add_in: TestAddIn, #[case] name: &str, #[case] expected: Option<usize>) {
+    assert_eq!(add_in.find_method(&os_string_nil(name)), expected);
+}
+
+#[rstest]
+fn test_names(add_in: TestAddIn) {
+    assert_eq!(add_in.get_prop_name(1, 0), Some(os_string_nil("LogLevel")));
+    assert_eq!(
+        add_in.get_prop_name(1, 1),
+        Some(os_string_nil("ЖурналУровень"))
+    );
+    assert_eq!(add_in.get_prop_name(2, 0), Some(os_string_nil("Running")));
+    assert_eq!(add_in.get_prop_name(3, 0), None);
+    assert_eq!(
+        add_in.get_method_name(1, 1),
+        Some(os_string_nil("ЖурналЗаписать"))
+    );
+    assert_eq!(add_in.get_method_name(2, 0), Some(os_string_nil("Start")));
+    assert_eq!(add_in.get_method_name(3, 0), None);
+}
+
+#[rstest]
+fn test_nested_props(mut add_in: TestAddIn) {
+    assert!(add_in.is_prop_readable(1));
+    assert!(add_in.is_prop_writable(1));
+    assert!(!add_in.is_prop_writable(2));
+
+    assert_eq!(add_in.set_prop_val(1, ParamValue::I32(3)), Ok(()));
+    assert_eq!(add_in.logger.level, 3);
+    assert_eq!(add_in.get_prop_val(1), Ok(ParamValue::I32(3)));
+    assert_eq!(add_in.set_prop_val(2, ParamValue::I32(3)), Err(()));
+    assert_eq!(
+        add_in.get_prop_val(0),
+        Ok(ParamValue::String(os_string("Main")))
+    );
+}
+
+#[rstest]
+fn test_nested_methods(mut add_in: TestAddIn) {
+    assert_eq!(add_in.get_n_params(1), 2);
+    assert_eq!(add_in.get_param_def_value(1, 1), Some(ParamValue::I32(1)));
+    assert!(add_in.has_ret_val(1));
+    assert!(!add_in.has_ret_val(2));
+
+    // default value of the nested method is filled
+    let mut params = ParamValues::new(vec![ParamValue::String(os_string("started"))]);
+    assert_eq!(add_in.call_as_func(1, &mut params), Ok(ParamValue::I32(1)));
+    assert_eq!(add_in.logger.lines, vec!["started".to_string()]);
+
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_proc(2, &mut params), Ok(()));
+    assert_eq!(add_in.call_as_func(2, &mut params), Err(()));
+    assert_eq!(add_in.jobs.running, 1);
+    assert_eq!(add_in.call_as_proc(3, &mut params), Err(()));
+}
+
+#[rstest]
+fn test_lifecycle_is_forwarded(mut add_in: TestAddIn) {
+    add_in.set_locale(&os_string("ru_RU"));
+    assert_eq!(add_in.logger.locale, "ru_RU");
+
+    add_in.done();
+    assert!(add_in.logger.flushed);
+}
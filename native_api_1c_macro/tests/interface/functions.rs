@@ -22,6 +22,12 @@ const PROCEDURE_NAME_RU: &str = "Процедура";
 const OUT_FUNCTION_NAME_EN: &str = "OutFunction";
 const OUT_FUNCTION_NAME_RU: &str = "ВыводФункция";
 
+const OPTIONAL_FUNCTION_NAME_EN: &str = "OptionalFunction";
+const OPTIONAL_FUNCTION_NAME_RU: &str = "НеобязательнаяФункция";
+
+const ANY_FUNCTION_NAME_EN: &str = "AnyFunction";
+const ANY_FUNCTION_NAME_RU: &str = "ЛюбаяФункция";
+
 const INVALID_NAME: &str = "Invalid";
 
 #[derive(AddIn)]
@@ -45,6 +51,16 @@ struct TestAddIn {
     #[add_in_func(name = OUT_FUNCTION_NAME_EN, name_ru = OUT_FUNCTION_NAME_RU)]
     #[arg(ty = Str, as_out, default = OUT_STR)]
     pub out_function: fn(&mut String),
+
+    #[add_in_func(name = OPTIONAL_FUNCTION_NAME_EN, name_ru = OPTIONAL_FUNCTION_NAME_RU)]
+    #[arg(ty = Int)]
+    #[returns(ty = Int)]
+    pub optional_function: fn(Option<i32>) -> Option<i32>,
+
+    #[add_in_func(name = ANY_FUNCTION_NAME_EN, name_ru = ANY_FUNCTION_NAME_RU)]
+    #[arg(ty = Any)]
+    #[returns(ty = Any)]
+    pub any_function: fn(ParamValue) -> ParamValue,
 }
 
 #[fixture]
@@ -59,12 +75,22 @@ fn add_in() -> TestAddIn {
         out_function: |out_str| {
             *out_str = format!("Hello, {out_str}!");
         },
+        optional_function: |a| a.map(|a| a * 2),
+        any_function: |a| {
+            if let Some(a) = a.as_i32() {
+                ParamValue::I32(a * 2)
+            } else if let Some(a) = a.as_str() {
+                ParamValue::String(os_string(&a.repeat(2)))
+            } else {
+                ParamValue::Empty
+            }
+        },
     }
 }
 
 #[rstest]
 fn test_get_n_methods(add_in: TestAddIn) {
-    assert_eq!(add_in.get_n_methods(), 3)
+    assert_eq!(add_in.get_n_methods(), 5)
 }
 
 #[rstest]
@@ -74,6 +100,10 @@ fn test_get_n_methods(add_in: TestAddIn) {
 #[case(PROCEDURE_NAME_RU, Some(1))]
 #[case(OUT_FUNCTION_NAME_EN, Some(2))]
 #[case(OUT_FUNCTION_NAME_RU, Some(2))]
+#[case(OPTIONAL_FUNCTION_NAME_EN, Some(3))]
+#[case(OPTIONAL_FUNCTION_NAME_RU, Some(3))]
+#[case(ANY_FUNCTION_NAME_EN, Some(4))]
+#[case(ANY_FUNCTION_NAME_RU, Some(4))]
 #[case(INVALID_NAME, None)]
 fn test_find_method(add_in: TestAddIn, #[case] name: &str, #[case] expected: Option<usize>) {
     use native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil;
@@ -91,7 +121,11 @@ fn test_find_method(add_in: TestAddIn, #[case] name: &str, #[case] expected: Opt
 #[case(2, 0, Some(OUT_FUNCTION_NAME_EN))]
 #[case(2, 1, Some(OUT_FUNCTION_NAME_RU))]
 #[case(2, 42, Some(OUT_FUNCTION_NAME_RU))]
-#[case(3, 0, None)]
+#[case(3, 0, Some(OPTIONAL_FUNCTION_NAME_EN))]
+#[case(3, 1, Some(OPTIONAL_FUNCTION_NAME_RU))]
+#[case(4, 0, Some(ANY_FUNCTION_NAME_EN))]
+#[case(4, 1, Some(ANY_FUNCTION_NAME_RU))]
+#[case(5, 0, None)]
 fn test_get_method_name(
     add_in: TestAddIn,
     #[case] method_i: usize,
@@ -110,7 +144,9 @@ fn test_get_method_name(
 #[case(0, 2)]
 #[case(1, 2)]
 #[case(2, 1)]
-#[case(3, 0)]
+#[case(3, 1)]
+#[case(4, 1)]
+#[case(5, 0)]
 fn test_get_n_params(add_in: TestAddIn, #[case] method_i: usize, #[case] n_params: usize) {
     assert_eq!(add_in.get_n_params(method_i), n_params);
 }
@@ -124,7 +160,9 @@ fn test_get_n_params(add_in: TestAddIn, #[case] method_i: usize, #[case] n_param
 #[case(1, 42, None)]
 #[case(2, 0, Some(ParamValue::String(os_string_nil(OUT_STR))))]
 #[case(2, 42, None)]
-#[case(3, 0, None)]
+#[case(3, 0, Some(ParamValue::Empty))]
+#[case(4, 0, None)]
+#[case(5, 0, None)]
 fn test_get_param_def_value(
     add_in: TestAddIn,
     #[case] method_i: usize,
@@ -138,7 +176,9 @@ fn test_get_param_def_value(
 #[case(0, true)]
 #[case(1, false)]
 #[case(2, false)]
-#[case(3, false)]
+#[case(3, true)]
+#[case(4, true)]
+#[case(5, false)]
 fn test_has_ret_val(add_in: TestAddIn, #[case] method_i: usize, #[case] has_ret_val: bool) {
     assert_eq!(add_in.has_ret_val(method_i), has_ret_val);
 }
@@ -183,3 +223,40 @@ fn test_call_out_function(mut add_in: TestAddIn) {
     assert!(result.is_ok());
     assert_eq!(params[0], ParamValue::String(os_string("Hello, 1C!")));
 }
+
+#[rstest]
+#[case(ParamValue::I32(21), ParamValue::I32(42))]
+#[case(ParamValue::Empty, ParamValue::Empty)]
+fn test_call_optional_function(
+    mut add_in: TestAddIn,
+    #[case] arg: ParamValue,
+    #[case] expected: ParamValue,
+) {
+    let mut params = ParamValues::new(vec![arg]);
+
+    let result = add_in.call_as_func(3, &mut params);
+    assert_eq!(result, Ok(expected));
+}
+
+#[rstest]
+fn test_call_optional_function_wrong_type(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::Bool(true)]);
+
+    let result = add_in.call_as_func(3, &mut params);
+    assert!(result.is_err());
+}
+
+#[rstest]
+#[case(ParamValue::I32(21), ParamValue::I32(42))]
+#[case(ParamValue::String(os_string("ab")), ParamValue::String(os_string("abab")))]
+#[case(ParamValue::Bool(true), ParamValue::Empty)]
+fn test_call_any_function(
+    mut add_in: TestAddIn,
+    #[case] arg: ParamValue,
+    #[case] expected: ParamValue,
+) {
+    let mut params = ParamValues::new(vec![arg]);
+
+    let result = add_in.call_as_func(4, &mut params);
+    assert_eq!(result, Ok(expected));
+}
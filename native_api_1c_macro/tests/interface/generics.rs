@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        string_utils::{os_string, os_string_nil},
+    },
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::{add_in_methods, AddIn};
+use rstest::rstest;
+
+trait Backend: Default {
+    const NAME: &'static str;
+
+    fn read(&mut self) -> i32;
+}
+
+#[derive(Default)]
+struct Device {
+    reads: i32,
+}
+
+impl Backend for Device {
+    const NAME: &'static str = "Device";
+
+    fn read(&mut self) -> i32 {
+        self.reads += 1;
+        self.reads
+    }
+}
+
+#[derive(Default)]
+struct Simulator;
+
+impl Backend for Simulator {
+    const NAME: &'static str = "Simulator";
+
+    fn read(&mut self) -> i32 {
+        42
+    }
+}
+
+#[derive(AddIn)]
+struct Driver<B>
+where
+    B: Backend,
+{
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Str, name = "Backend", name_ru = "Бэкенд", get = Self::backend_name)]
+    backend: B,
+
+    #[add_in_func(name = "Read", name_ru = "Прочитать")]
+    #[returns(ty = Int)]
+    read: fn(&mut Self) -> i32,
+}
+
+impl<B: Backend> Driver<B> {
+    fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            backend: B::default(),
+            read: |driver| driver.backend.read(),
+        }
+    }
+
+    fn backend_name(&self) -> String {
+        B::NAME.to_string()
+    }
+}
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct Labeled<'a, const N: usize> {
+    #[add_in_prop(ty = Str, name = "Label", name_ru = "Метка", readable)]
+    label: &'a str,
+
+    values: [i32; N],
+}
+
+#[add_in_methods]
+impl<'a, const N: usize> Labeled<'a, N> {
+    #[add_in_func(name = "Sum", name_ru = "Сумма")]
+    fn sum(&self) -> i32 {
+        self.values.iter().sum()
+    }
+}
+
+#[rstest]
+fn test_generic_props() {
+    let device = Driver::<Device>::new();
+    let simulator = Driver::<Simulator>::new();
+
+    assert_eq!(device.find_prop(&os_string_nil("Бэкенд")), Some(0));
+    assert_eq!(
+        device.get_prop_val(0),
+        Ok(ParamValue::String(os_string("Device")))
+    );
+    assert_eq!(
+        simulator.get_prop_val(0),
+        Ok(ParamValue::String(os_string("Simulator")))
+    );
+}
+
+#[rstest]
+fn test_generic_methods() {
+    let mut device = Driver::<Device>::new();
+    let mut simulator = Driver::<Simulator>::new();
+
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(device.call_as_func(0, &mut params), Ok(ParamValue::I32(1)));
+    assert_eq!(device.call_as_func(0, &mut params), Ok(ParamValue::I32(2)));
+    assert_eq!(
+        simulator.call_as_func(0, &mut params),
+        Ok(ParamValue::I32(42))
+    );
+}
+
+#[rstest]
+fn test_lifetime_and_const_generics() {
+    let mut add_in = Labeled {
+        label: "Totals",
+        values: [1, 2, 3],
+    };
+
+    assert_eq!(
+        add_in.get_prop_val(0),
+        Ok(ParamValue::String(os_string("Totals")))
+    );
+    assert_eq!(add_in.find_method(&os_string_nil("Сумма")), Some(0));
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Ok(ParamValue::I32(6)));
+}
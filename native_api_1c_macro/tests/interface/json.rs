@@ -0,0 +1,185 @@
+mod common;
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use chrono::NaiveDateTime;
+use common::{platform, FakePlatform};
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{json, AddInWrapper, Coercion, FromParamValue, Json, ParamValue, ParamValues},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Item {
+    name: String,
+    amount: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Order {
+    id: u32,
+    #[serde(with = "json::date")]
+    date: NaiveDateTime,
+    #[serde(with = "json::optional_date", default)]
+    shipped: Option<NaiveDateTime>,
+    items: Vec<Item>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct Total {
+    id: u32,
+    total: f64,
+}
+
+/// Map with non-string keys, that can't be serialized into JSON
+type Pairs = BTreeMap<(u32, u32), u32>;
+
+#[derive(AddIn)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Json, name = "LastOrder", name_ru = "ПоследнийЗаказ", readable, writable)]
+    last_order: Json<Order>,
+
+    #[add_in_func(name = "Total", name_ru = "Итог")]
+    #[arg(ty = Json)]
+    #[returns(ty = Json)]
+    pub total: fn(Json<Order>) -> Json<Total>,
+
+    #[add_in_func(name = "Find", name_ru = "Найти")]
+    #[arg(ty = Json)]
+    #[returns(ty = Json)]
+    pub find: fn(&Self, Json<u32>) -> Option<Json<Order>>,
+
+    #[add_in_func(name = "Pairs", name_ru = "Пары")]
+    #[returns(ty = Json)]
+    pub pairs: fn() -> Json<Pairs>,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        last_order: Json(Order {
+            id: 1,
+            date: date("2024-01-31T12:00:00"),
+            shipped: None,
+            items: vec![],
+        }),
+        total: |order| {
+            Json(Total {
+                id: order.id,
+                total: order.items.iter().map(|item| item.amount).sum(),
+            })
+        },
+        find: |addin, id| (*id == addin.last_order.id).then(|| addin.last_order.clone()),
+        pairs: || Json(BTreeMap::from([((1, 2), 3)])),
+    }
+}
+
+fn date(value: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap()
+}
+
+fn string(value: &str) -> ParamValue {
+    ParamValue::String(os_string(value))
+}
+
+const ORDER_JSON: &str = r#"{
+    "id": 7,
+    "date": "2024-02-01T09:30:00",
+    "shipped": "2024-02-02T10:00:00",
+    "items": [
+        {"name": "Apple", "amount": 10.5},
+        {"name": "Pear", "amount": 2}
+    ]
+}"#;
+
+#[rstest]
+fn test_get_prop_val(add_in: TestAddIn) {
+    assert_eq!(
+        add_in.get_prop_val(0),
+        Ok(string(
+            r#"{"id":1,"date":"2024-01-31T12:00:00","shipped":null,"items":[]}"#
+        ))
+    );
+}
+
+#[rstest]
+fn test_set_prop_val(mut add_in: TestAddIn) {
+    assert_eq!(add_in.set_prop_val(0, string(ORDER_JSON)), Ok(()));
+    assert_eq!(add_in.last_order.id, 7);
+    assert_eq!(add_in.last_order.date, date("2024-02-01T09:30:00"));
+    assert_eq!(add_in.last_order.shipped, Some(date("2024-02-02T10:00:00")));
+    assert_eq!(add_in.last_order.items[1].name, "Pear");
+}
+
+#[rstest]
+#[case(string("{"))]
+#[case(string("[]"))]
+#[case(ParamValue::I32(1))]
+fn test_set_prop_val_invalid(mut add_in: TestAddIn, #[case] value: ParamValue) {
+    assert_eq!(add_in.set_prop_val(0, value), Err(()));
+    assert_eq!(add_in.last_order.id, 1);
+}
+
+#[rstest]
+fn test_call_total(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![string(ORDER_JSON)]);
+    assert_eq!(
+        add_in.call_as_func(0, &mut params),
+        Ok(string(r#"{"id":7,"total":12.5}"#))
+    );
+}
+
+#[rstest]
+#[case(string("2"), ParamValue::Empty)]
+#[case(
+    string("1"),
+    string(r#"{"id":1,"date":"2024-01-31T12:00:00","shipped":null,"items":[]}"#)
+)]
+fn test_call_find(mut add_in: TestAddIn, #[case] id: ParamValue, #[case] expected: ParamValue) {
+    let mut params = ParamValues::new(vec![id]);
+    assert_eq!(add_in.call_as_func(1, &mut params), Ok(expected));
+}
+
+#[rstest]
+#[case(
+    r#"{"id": 1, "date": "2024-01-31T12:00:00", "items": [{"name": "Apple", "amount": "ten"}]}"#,
+    "invalid JSON at `items[0].amount`: invalid type: string \"ten\", expected f64 at line 1 column 84"
+)]
+#[case(r#"{"id": 1, "date": "31.01.2024", "items": []}"#, "invalid JSON at `date`: invalid date `31.01.2024`, expected `YYYY-MM-DDThh:mm:ss` at line 1 column 30")]
+#[case(
+    r#"{"id": 1, "date": "2024-01-31T12:00:00Z", "items": []}"#,
+    "invalid JSON at `date`: date `2024-01-31T12:00:00Z` has time zone, which is not supported, \
+    write dates with `ВариантЗаписиДатыJSON.ЛокальнаяДата` at line 1 column 40"
+)]
+#[case(
+    r#"{"id": 1, "date": "2024-01-31T12:00:00", "shipped": "2024-01-31T12:00:00+03:00", "items": []}"#,
+    "invalid JSON at `shipped`: date `2024-01-31T12:00:00+03:00` has time zone, which is not supported, \
+    write dates with `ВариантЗаписиДатыJSON.ЛокальнаяДата` at line 1 column 79"
+)]
+#[case(
+    r#"{"id": 1, "date": "2024-01-31T12:00:00", "items": []} 1"#,
+    "invalid JSON: trailing characters at line 1 column 55"
+)]
+#[case("", "invalid JSON: EOF while parsing a value at line 1 column 0")]
+fn test_error_path(#[case] value: &str, #[case] expected: &str) {
+    let result = Json::<Order>::from_param_value(&string(value), Coercion::default());
+    assert_eq!(result.unwrap_err().to_string(), expected);
+}
+
+#[rstest]
+fn test_serialization_error_reported(mut add_in: TestAddIn, platform: &'static FakePlatform) {
+    assert!(add_in.init(platform.connection()));
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_func(2, &mut params), Err(()));
+    assert_eq!(
+        platform.state().errors[0].description,
+        "Pairs: returned value can't serialize JSON: key must be a string"
+    );
+}
@@ -0,0 +1,147 @@
+use std::{
+    ffi::c_void,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, create_component, destroy_component, string_utils::os_string},
+    interface::AddInWrapper,
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+use utf16_lit::utf16_null;
+
+#[derive(AddIn)]
+#[add_in(
+    name = "PrinterDriver",
+    on_init = Self::start,
+    on_done = Self::stop,
+    on_locale = Self::relocalize,
+    on_language = Self::set_language
+)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Str, name = "Greeting", name_ru = "Приветствие", readable)]
+    greeting: String,
+
+    started: bool,
+    flushed: Vec<String>,
+}
+
+impl TestAddIn {
+    fn start(&mut self) -> Result<(), String> {
+        if self.started {
+            return Err("printer is already started".to_string());
+        }
+        self.started = true;
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.flushed.push(std::mem::take(&mut self.greeting));
+        self.started = false;
+    }
+
+    fn relocalize(&mut self, locale: &str) {
+        let language = locale.split('_').next().unwrap_or_default();
+        self.set_language(language);
+    }
+
+    fn set_language(&mut self, language: &str) {
+        self.greeting = match language {
+            "ru" => "Привет".to_string(),
+            _ => "Hello".to_string(),
+        };
+    }
+}
+
+#[derive(AddIn)]
+struct DefaultAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        greeting: String::new(),
+        started: false,
+        flushed: vec![],
+    }
+}
+
+#[rstest]
+fn test_register_extension_as(mut add_in: TestAddIn) {
+    assert_eq!(add_in.register_extension_as(), utf16_null!("PrinterDriver"));
+
+    let mut default_add_in = DefaultAddIn {
+        connection: Arc::new(None),
+    };
+    assert_eq!(
+        default_add_in.register_extension_as(),
+        utf16_null!("DefaultAddIn")
+    );
+}
+
+#[rstest]
+#[case("ru_RU", "Привет")]
+#[case("en_US", "Hello")]
+fn test_set_locale(mut add_in: TestAddIn, #[case] locale: &str, #[case] greeting: &str) {
+    add_in.set_locale(&os_string(locale));
+    assert_eq!(add_in.greeting, greeting);
+}
+
+#[rstest]
+#[case("ru", "Привет")]
+#[case("en", "Hello")]
+fn test_set_user_interface_language_code(
+    mut add_in: TestAddIn,
+    #[case] language: &str,
+    #[case] greeting: &str,
+) {
+    add_in.set_user_interface_language_code(&os_string(language));
+    assert_eq!(add_in.greeting, greeting);
+}
+
+#[rstest]
+fn test_done(mut add_in: TestAddIn) {
+    add_in.set_locale(&os_string("en_US"));
+    add_in.started = true;
+
+    add_in.done();
+    assert!(!add_in.started);
+    assert_eq!(add_in.flushed, vec!["Hello".to_string()]);
+}
+
+#[derive(AddIn)]
+struct DroppedAddIn {
+    dropped: Arc<AtomicBool>,
+}
+
+impl Drop for DroppedAddIn {
+    fn drop(&mut self) {
+        self.dropped.store(true, Ordering::Relaxed);
+    }
+}
+
+#[rstest]
+fn test_destroy_component() {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let mut component: *mut c_void = ptr::null_mut();
+    let add_in = DroppedAddIn {
+        dropped: dropped.clone(),
+    };
+    unsafe {
+        assert_eq!(create_component(&mut component, add_in), 1);
+        assert_eq!(destroy_component(&mut component), 0);
+    }
+    assert!(component.is_null());
+    assert!(dropped.load(Ordering::Relaxed));
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        string_utils::{os_string, os_string_nil},
+    },
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::{add_in_methods, AddIn};
+use rstest::{fixture, rstest};
+
+const DEFAULT_VALUE: i32 = 12;
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = "Storage", name_ru = "Хранилище", readable)]
+    storage: i32,
+}
+
+#[add_in_methods]
+impl TestAddIn {
+    /// Adds arguments to the storage
+    #[add_in_func(name = "Function", name_ru = "Функция")]
+    #[arg(ty = Int)]
+    #[arg(ty = Int, default = DEFAULT_VALUE)]
+    #[returns(ty = Int, result)]
+    fn function(&self, a: i32, b: i32) -> Result<i32, ()> {
+        a.checked_add(b)
+            .and_then(|sum| sum.checked_add(self.storage))
+            .ok_or(())
+    }
+
+    #[add_in_func(name = "Procedure", name_ru = "Процедура")]
+    fn procedure(&mut self, a: i32, b: i32) {
+        self.storage = a + b;
+    }
+
+    #[add_in_func(name = "Greet", name_ru = "Поприветствовать")]
+    #[arg(default = "world")]
+    fn greet(name: &mut String) {
+        *name = format!("Hello, {name}!");
+    }
+
+    #[add_in_func(name = "Double", name_ru = "Удвоить")]
+    pub fn double(value: Option<i64>) -> Option<i64> {
+        value.map(|value| value * 2)
+    }
+
+    /// Not visible from 1C
+    fn helper(&self) -> i32 {
+        self.storage
+    }
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        storage: 0,
+    }
+}
+
+#[rstest]
+fn test_get_n_methods(add_in: TestAddIn) {
+    assert_eq!(add_in.get_n_methods(), 4);
+}
+
+#[rstest]
+#[case("Function", Some(0))]
+#[case("Процедура", Some(1))]
+#[case("Greet", Some(2))]
+#[case("Удвоить", Some(3))]
+#[case("helper", None)]
+fn test_find_method(add_in: TestAddIn, #[case] name: &str, #[case] expected: Option<usize>) {
+    assert_eq!(add_in.find_method(&os_string_nil(name)), expected);
+}
+
+#[rstest]
+#[case(0, 2)]
+#[case(1, 2)]
+#[case(2, 1)]
+#[case(3, 1)]
+fn test_get_n_params(add_in: TestAddIn, #[case] method_i: usize, #[case] n_params: usize) {
+    assert_eq!(add_in.get_n_params(method_i), n_params);
+}
+
+#[rstest]
+fn test_get_param_def_value(add_in: TestAddIn) {
+    assert_eq!(
+        add_in.get_param_def_value(0, 1),
+        Some(ParamValue::I32(DEFAULT_VALUE))
+    );
+    assert_eq!(
+        add_in.get_param_def_value(2, 0),
+        Some(ParamValue::String(os_string("world")))
+    );
+}
+
+#[rstest]
+fn test_call_methods(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(1), ParamValue::I32(2)]);
+    assert_eq!(add_in.call_as_proc(1, &mut params), Ok(()));
+    assert_eq!(add_in.helper(), 3);
+
+    let mut params = ParamValues::new(vec![ParamValue::I32(1), ParamValue::I32(2)]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Ok(ParamValue::I32(6)));
+
+    let mut params = ParamValues::new(vec![ParamValue::I32(i32::MAX), ParamValue::I32(2)]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Err(()));
+}
+
+#[rstest]
+fn test_call_out_param(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::String(os_string("1C"))]);
+    assert_eq!(add_in.call_as_proc(2, &mut params), Ok(()));
+    assert_eq!(params[0], ParamValue::String(os_string("Hello, 1C!")));
+}
+
+#[rstest]
+#[case(ParamValue::I32(21), ParamValue::I32(42))]
+#[case(ParamValue::Empty, ParamValue::Empty)]
+fn test_call_static(
+    mut add_in: TestAddIn,
+    #[case] value: ParamValue,
+    #[case] expected: ParamValue,
+) {
+    let mut params = ParamValues::new(vec![value]);
+    assert_eq!(add_in.call_as_func(3, &mut params), Ok(expected));
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use std::{str::FromStr, sync::Arc};
+
+use common::{platform, FakePlatform};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue, ParamValues},
+    rust_decimal::Decimal,
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+const BIG_NUMBER: i64 = 1 << 40;
+const HUGE_NUMBER: i64 = (1 << 60) + 1;
+
+#[derive(AddIn)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = UInt64, name = "FileSize", name_ru = "РазмерФайла", readable, writable)]
+    file_size: u64,
+
+    #[add_in_prop(ty = Decimal, name = "Amount", name_ru = "Сумма", readable, writable)]
+    amount: Decimal,
+
+    #[add_in_func(name = "Shift", name_ru = "Сдвиг")]
+    #[arg(ty = Int64)]
+    #[arg(ty = UInt8, default = 1)]
+    #[returns(ty = Int64)]
+    pub shift: fn(i64, u8) -> i64,
+
+    #[add_in_func(name = "Negate", name_ru = "Отрицание")]
+    #[arg(ty = Int16, as_out)]
+    pub negate: fn(&mut i16),
+
+    #[add_in_func(name = "AddAmounts", name_ru = "СложитьСуммы")]
+    #[arg(ty = Decimal)]
+    #[arg(ty = Decimal)]
+    #[returns(ty = Decimal)]
+    pub add_amounts: fn(Decimal, Decimal) -> Decimal,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        file_size: 0,
+        amount: Decimal::ZERO,
+        shift: |value, bits| value << bits,
+        negate: |value| *value = -*value,
+        add_amounts: |a, b| a + b,
+    }
+}
+
+const MAX_EXACT: i64 = (1 << 53) - 1;
+
+#[rstest]
+#[case(MAX_EXACT, ParamValue::F64(MAX_EXACT as f64))]
+#[case(MAX_EXACT + 1, ParamValue::I64(MAX_EXACT + 1))]
+#[case(-MAX_EXACT, ParamValue::F64(-MAX_EXACT as f64))]
+#[case(-MAX_EXACT - 1, ParamValue::I64(-MAX_EXACT - 1))]
+fn test_from_i64_exact_boundary(#[case] value: i64, #[case] expected: ParamValue) {
+    assert_eq!(ParamValue::from_i64(value), expected);
+}
+
+#[rstest]
+#[case(MAX_EXACT as u64, ParamValue::F64(MAX_EXACT as f64))]
+#[case(MAX_EXACT as u64 + 1, ParamValue::I64(MAX_EXACT + 1))]
+#[case(i64::MAX as u64, ParamValue::I64(i64::MAX))]
+#[case(i64::MAX as u64 + 1, ParamValue::F64(i64::MAX as f64))]
+fn test_from_u64_exact_boundary(#[case] value: u64, #[case] expected: ParamValue) {
+    assert_eq!(ParamValue::from_u64(value), expected);
+}
+
+#[rstest]
+#[case(42, ParamValue::I32(42))]
+#[case(BIG_NUMBER as u64, ParamValue::F64(BIG_NUMBER as f64))]
+#[case(HUGE_NUMBER as u64, ParamValue::I64(HUGE_NUMBER))]
+#[case(u64::MAX, ParamValue::F64(u64::MAX as f64))]
+fn test_get_prop_val(mut add_in: TestAddIn, #[case] value: u64, #[case] expected: ParamValue) {
+    add_in.file_size = value;
+    assert_eq!(add_in.get_prop_val(0), Ok(expected));
+}
+
+#[rstest]
+#[case(ParamValue::I32(42), Ok(42))]
+#[case(ParamValue::F64(BIG_NUMBER as f64), Ok(BIG_NUMBER as u64))]
+#[case(ParamValue::I64(HUGE_NUMBER), Ok(HUGE_NUMBER as u64))]
+#[case(ParamValue::I32(-1), Err(()))]
+#[case(ParamValue::F64(1.5), Err(()))]
+#[case(ParamValue::F64(-1.0), Err(()))]
+#[case(ParamValue::Bool(true), Err(()))]
+fn test_set_prop_val(
+    mut add_in: TestAddIn,
+    #[case] value: ParamValue,
+    #[case] expected: Result<u64, ()>,
+) {
+    let result = add_in.set_prop_val(0, value);
+    assert_eq!(result.map(|_| add_in.file_size), expected);
+}
+
+#[rstest]
+fn test_get_param_def_value(add_in: TestAddIn) {
+    assert_eq!(add_in.get_param_def_value(0, 1), Some(ParamValue::I32(1)));
+}
+
+#[rstest]
+#[case(ParamValue::I32(1), ParamValue::I32(1), Ok(ParamValue::I32(2)))]
+#[case(ParamValue::I32(1), ParamValue::I32(40), Ok(ParamValue::F64(BIG_NUMBER as f64)))]
+#[case(ParamValue::F64(BIG_NUMBER as f64), ParamValue::I32(20), Ok(ParamValue::I64(1 << 60)))]
+#[case(ParamValue::I32(1), ParamValue::I32(256), Err(()))]
+#[case(ParamValue::I32(1), ParamValue::I32(-1), Err(()))]
+fn test_call_shift(
+    mut add_in: TestAddIn,
+    #[case] value: ParamValue,
+    #[case] bits: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    let mut params = ParamValues::new(vec![value, bits]);
+    assert_eq!(add_in.call_as_func(0, &mut params), expected);
+}
+
+#[rstest]
+#[case(ParamValue::I32(5), Ok(ParamValue::I32(-5)))]
+#[case(ParamValue::I32(i16::MAX as i32 + 1), Err(()))]
+fn test_call_negate(
+    mut add_in: TestAddIn,
+    #[case] value: ParamValue,
+    #[case] expected: Result<ParamValue, ()>,
+) {
+    let mut params = ParamValues::new(vec![value]);
+    let result = add_in.call_as_proc(1, &mut params);
+    assert_eq!(result.map(|_| params[0].clone()), expected);
+}
+
+fn decimal_string(value: &str) -> ParamValue {
+    ParamValue::String(os_string(value))
+}
+
+#[rstest]
+fn test_get_decimal_prop_val(mut add_in: TestAddIn) {
+    add_in.amount = Decimal::from_str("12345678901234567890.123456789").unwrap();
+    assert_eq!(
+        add_in.get_prop_val(1),
+        Ok(decimal_string("12345678901234567890.123456789"))
+    );
+}
+
+#[rstest]
+#[case(decimal_string("0.1"), Ok("0.1"))]
+#[case(
+    decimal_string(" -792281625142643375935439503.35 "),
+    Ok("-792281625142643375935439503.35")
+)]
+#[case(decimal_string("12345678901234567890.1234567890"), Err(()))]
+#[case(ParamValue::I32(42), Ok("42"))]
+#[case(ParamValue::F64(0.1), Ok("0.1"))]
+#[case(decimal_string("1,5"), Err(()))]
+#[case(ParamValue::Bool(true), Err(()))]
+fn test_set_decimal_prop_val(
+    mut add_in: TestAddIn,
+    #[case] value: ParamValue,
+    #[case] expected: Result<&str, ()>,
+) {
+    let result = add_in.set_prop_val(1, value);
+    assert_eq!(
+        result.map(|_| add_in.amount),
+        expected.map(|expected| Decimal::from_str(expected).unwrap())
+    );
+}
+
+#[rstest]
+#[case(
+    decimal_string("12345678901234567890.1234567890"),
+    "number 12345678901234567890.1234567890 doesn't fit into Decimal, \
+    which holds at most 28 significant digits"
+)]
+#[case(
+    ParamValue::F64(1e30),
+    "number 1000000000000000000000000000000 doesn't fit into Decimal, \
+    which holds at most 28 significant digits"
+)]
+#[case(decimal_string("1e5"), "expected Number, got String")]
+fn test_set_decimal_prop_val_error_reported(
+    mut add_in: TestAddIn,
+    platform: &'static FakePlatform,
+    #[case] value: ParamValue,
+    #[case] expected: &str,
+) {
+    assert!(add_in.init(platform.connection()));
+    assert!(add_in.set_prop_val(1, value).is_err());
+    assert_eq!(add_in.amount, Decimal::ZERO);
+    assert_eq!(
+        platform.state().errors[0].description,
+        format!("Amount: {expected}")
+    );
+}
+
+#[rstest]
+fn test_call_add_amounts(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![decimal_string("0.1"), decimal_string("0.20")]);
+    assert_eq!(
+        add_in.call_as_func(2, &mut params),
+        Ok(decimal_string("0.3"))
+    );
+}
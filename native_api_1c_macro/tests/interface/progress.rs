@@ -0,0 +1,110 @@
+mod common;
+
+use std::{sync::Arc, thread, time::Duration};
+
+use common::{platform, FakePlatform};
+use native_api_1c::native_api_1c_core::interface::{ConnectionHandle, Progress};
+use rstest::rstest;
+
+fn shown(text: &str) -> Option<String> {
+    Some(text.to_string())
+}
+
+#[rstest]
+fn test_message(platform: &'static FakePlatform) {
+    let progress = Progress::new(platform.handle(), "Importing", 10000);
+    assert_eq!(progress.message(), "Importing: 0% (0/10000)");
+    progress.set(4500);
+    assert_eq!(progress.message(), "Importing: 45% (4500/10000)");
+
+    let progress = Progress::new(platform.handle(), "Scanning", 0);
+    progress.inc(7);
+    assert_eq!(progress.message(), "Scanning: 7");
+}
+
+#[rstest]
+fn test_updates_on_percent_change(platform: &'static FakePlatform) {
+    let progress = Progress::new(platform.handle(), "Importing", 1000).interval(Duration::ZERO);
+    for _ in 0..25 {
+        progress.inc(1);
+    }
+    assert_eq!(
+        platform.state().status_lines.clone(),
+        vec![
+            shown("Importing: 0% (1/1000)"),
+            shown("Importing: 1% (10/1000)"),
+            shown("Importing: 2% (20/1000)")
+        ]
+    );
+
+    drop(progress);
+    assert_eq!(platform.state().status_lines.last(), Some(&None));
+}
+
+#[rstest]
+fn test_throttled_by_interval(platform: &'static FakePlatform) {
+    let progress =
+        Progress::new(platform.handle(), "Importing", 100).interval(Duration::from_secs(3600));
+    progress.set(10);
+    progress.set(50);
+    // completion is not throttled
+    progress.set(100);
+    assert_eq!(
+        platform.state().status_lines.clone(),
+        vec![
+            shown("Importing: 10% (10/100)"),
+            shown("Importing: 100% (100/100)")
+        ]
+    );
+}
+
+#[rstest]
+fn test_older_value_skipped(platform: &'static FakePlatform) {
+    let progress = Progress::new(platform.handle(), "Importing", 100).interval(Duration::ZERO);
+    progress.set(100);
+    // slower worker reports its value after completion
+    progress.set(60);
+    assert_eq!(
+        platform.state().status_lines,
+        vec![shown("Importing: 100% (100/100)")]
+    );
+}
+
+#[rstest]
+fn test_background_workers(platform: &'static FakePlatform) {
+    let progress = Arc::new(Progress::new(platform.handle(), "Scanning", 400));
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let progress = progress.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    progress.inc(1);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert_eq!(progress.current(), 400);
+    assert_eq!(
+        platform.state().status_lines.last(),
+        Some(&shown("Scanning: 100% (400/400)"))
+    );
+
+    drop(progress);
+    assert_eq!(platform.state().status_lines.last(), Some(&None));
+}
+
+#[rstest]
+fn test_without_connection() {
+    let progress = Progress::new(ConnectionHandle::new(), "Importing", 100);
+    progress.set(50);
+    assert_eq!(progress.message(), "Importing: 50% (50/100)");
+}
+
+#[rstest]
+fn test_nothing_shown_no_reset(platform: &'static FakePlatform) {
+    drop(Progress::new(platform.handle(), "Importing", 100));
+    assert!(platform.state().status_lines.is_empty());
+}
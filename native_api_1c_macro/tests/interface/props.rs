@@ -1,5 +1,8 @@
+mod common;
+
 use std::sync::Arc;
 
+use common::{platform, FakePlatform};
 use native_api_1c::native_api_1c_core::{
     ffi::connection::Connection,
     interface::{AddInWrapper, ParamValue},
@@ -16,12 +19,29 @@ const R_PROP_NAME_RU: &str = "СвойствоТолькоЧтение";
 const W_PROP_NAME: &str = "WriteOnlyProperty";
 const W_PROP_NAME_RU: &str = "СвойствоТолькоЗапись";
 
+const VALIDATED_PROP_NAME: &str = "ValidatedProperty";
+const VALIDATED_PROP_NAME_RU: &str = "ПроверяемоеСвойство";
+
+const COMPUTED_PROP_NAME: &str = "ComputedProperty";
+const COMPUTED_PROP_NAME_RU: &str = "ВычисляемоеСвойство";
+
+const OPTIONAL_PROP_NAME: &str = "OptionalProperty";
+const OPTIONAL_PROP_NAME_RU: &str = "НеобязательноеСвойство";
+
 const INVALID_PROP_NAME: &str = "InvalidProperty";
 
 const START_VALUE: i32 = 42;
 const NEW_VALUE: i32 = 24;
 
 #[derive(AddIn)]
+#[add_in_prop(
+    ty = Int,
+    name = COMPUTED_PROP_NAME,
+    name_ru = COMPUTED_PROP_NAME_RU,
+    get = Self::doubled,
+    set = Self::set_doubled,
+    result
+)]
 struct TestAddIn {
     #[add_in_con]
     connection: Arc<Option<&'static Connection>>,
@@ -34,6 +54,40 @@ struct TestAddIn {
 
     #[add_in_prop(ty = Int, name = W_PROP_NAME, name_ru = W_PROP_NAME_RU, writable)]
     w_property: i32,
+
+    #[add_in_prop(
+        ty = Int,
+        name = VALIDATED_PROP_NAME,
+        name_ru = VALIDATED_PROP_NAME_RU,
+        readable,
+        set = Self::set_validated,
+        result
+    )]
+    validated_property: i32,
+}
+
+impl TestAddIn {
+    fn set_validated(&mut self, value: i32) -> Result<(), String> {
+        if value < 0 {
+            return Err(format!("{value} is negative"));
+        }
+        self.validated_property = value;
+        Ok(())
+    }
+
+    fn doubled(&self) -> Result<i32, String> {
+        self.rw_property
+            .checked_mul(2)
+            .ok_or_else(|| "overflow".to_string())
+    }
+
+    fn set_doubled(&mut self, value: i32) -> Result<(), String> {
+        if value % 2 != 0 {
+            return Err(format!("{value} is odd"));
+        }
+        self.rw_property = value / 2;
+        Ok(())
+    }
 }
 
 #[fixture]
@@ -43,12 +97,13 @@ fn add_in() -> TestAddIn {
         rw_property: START_VALUE,
         r_property: START_VALUE,
         w_property: START_VALUE,
+        validated_property: START_VALUE,
     }
 }
 
 #[rstest]
 fn test_get_n_props(add_in: TestAddIn) {
-    assert_eq!(add_in.get_n_props(), 3)
+    assert_eq!(add_in.get_n_props(), 5)
 }
 
 #[rstest]
@@ -58,6 +113,10 @@ fn test_get_n_props(add_in: TestAddIn) {
 #[case(R_PROP_NAME_RU, Some(1))]
 #[case(W_PROP_NAME, Some(2))]
 #[case(W_PROP_NAME_RU, Some(2))]
+#[case(VALIDATED_PROP_NAME, Some(3))]
+#[case(VALIDATED_PROP_NAME_RU, Some(3))]
+#[case(COMPUTED_PROP_NAME, Some(4))]
+#[case(COMPUTED_PROP_NAME_RU, Some(4))]
 #[case(INVALID_PROP_NAME, None)]
 fn test_find_prop(add_in: TestAddIn, #[case] prop_name: &str, #[case] prop_index: Option<usize>) {
     use native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil;
@@ -75,6 +134,9 @@ fn test_find_prop(add_in: TestAddIn, #[case] prop_name: &str, #[case] prop_index
 #[case(2, 0, Some(W_PROP_NAME))]
 #[case(2, 1, Some(W_PROP_NAME_RU))]
 #[case(2, 43, Some(W_PROP_NAME_RU))]
+#[case(3, 0, Some(VALIDATED_PROP_NAME))]
+#[case(4, 1, Some(COMPUTED_PROP_NAME_RU))]
+#[case(5, 0, None)]
 fn test_get_prop_name(
     add_in: TestAddIn,
     #[case] prop_index: usize,
@@ -91,6 +153,9 @@ fn test_get_prop_name(
 #[case(0, true)]
 #[case(1, true)]
 #[case(2, false)]
+#[case(3, true)]
+#[case(4, true)]
+#[case(5, false)]
 fn test_is_prop_readable(add_in: TestAddIn, #[case] prop_index: usize, #[case] readable: bool) {
     assert_eq!(add_in.is_prop_readable(prop_index), readable);
 }
@@ -99,6 +164,9 @@ fn test_is_prop_readable(add_in: TestAddIn, #[case] prop_index: usize, #[case] r
 #[case(0, true)]
 #[case(1, false)]
 #[case(2, true)]
+#[case(3, true)]
+#[case(4, true)]
+#[case(5, false)]
 fn test_is_prop_writable(add_in: TestAddIn, #[case] prop_index: usize, #[case] writable: bool) {
     assert_eq!(add_in.is_prop_writable(prop_index), writable);
 }
@@ -107,6 +175,9 @@ fn test_is_prop_writable(add_in: TestAddIn, #[case] prop_index: usize, #[case] w
 #[case(0, Some(START_VALUE))]
 #[case(1, Some(START_VALUE))]
 #[case(2, None)]
+#[case(3, Some(START_VALUE))]
+#[case(4, Some(START_VALUE * 2))]
+#[case(5, None)]
 fn test_get_prop_val(
     add_in: TestAddIn,
     #[case] prop_i: usize,
@@ -126,6 +197,8 @@ fn test_get_prop_val(
 #[case(0, Ok(()), |add_in: &TestAddIn| add_in.rw_property, NEW_VALUE)]
 #[case(1, Err(()), |add_in: &TestAddIn| add_in.r_property, START_VALUE)]
 #[case(2, Ok(()), |add_in: &TestAddIn| add_in.w_property, NEW_VALUE)]
+#[case(3, Ok(()), |add_in: &TestAddIn| add_in.validated_property, NEW_VALUE)]
+#[case(4, Ok(()), |add_in: &TestAddIn| add_in.rw_property, NEW_VALUE / 2)]
 fn test_set_prop_val(
     mut add_in: TestAddIn,
     #[case] prop_i: usize,
@@ -139,3 +212,157 @@ fn test_set_prop_val(
     );
     assert_eq!(value_getter(&add_in), new_value);
 }
+
+#[rstest]
+#[case(3, -1, |add_in: &TestAddIn| add_in.validated_property)]
+#[case(4, NEW_VALUE + 1, |add_in: &TestAddIn| add_in.rw_property)]
+fn test_set_prop_val_rejected(
+    mut add_in: TestAddIn,
+    #[case] prop_i: usize,
+    #[case] new_value: i32,
+    #[case] value_getter: fn(&TestAddIn) -> i32,
+) {
+    assert_eq!(
+        add_in.set_prop_val(prop_i, ParamValue::I32(new_value)),
+        Err(())
+    );
+    assert_eq!(value_getter(&add_in), START_VALUE);
+}
+
+#[rstest]
+fn test_get_computed_prop_val_error(mut add_in: TestAddIn) {
+    add_in.rw_property = i32::MAX;
+    assert_eq!(add_in.get_prop_val(4), Err(()));
+}
+
+#[rstest]
+#[case(3, ParamValue::I32(-1), "ValidatedProperty: -1 is negative")]
+#[case(4, ParamValue::I32(NEW_VALUE + 1), "ComputedProperty: 25 is odd")]
+#[case(0, ParamValue::Bool(true), "Property: expected Number, got Boolean")]
+fn test_set_prop_val_error_reported(
+    mut add_in: TestAddIn,
+    platform: &'static FakePlatform,
+    #[case] prop_i: usize,
+    #[case] value: ParamValue,
+    #[case] description: &str,
+) {
+    assert!(add_in.init(platform.connection()));
+    assert_eq!(add_in.set_prop_val(prop_i, value), Err(()));
+
+    let errors = &platform.state().errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].source, "TestAddIn");
+    assert_eq!(errors[0].description, description);
+}
+
+#[rstest]
+fn test_get_prop_val_error_reported(mut add_in: TestAddIn, platform: &'static FakePlatform) {
+    assert!(add_in.init(platform.connection()));
+    add_in.rw_property = i32::MAX;
+    assert_eq!(add_in.get_prop_val(4), Err(()));
+
+    let errors = &platform.state().errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].description, "ComputedProperty: overflow");
+}
+
+/// Getter can't fail, setter validates the value
+#[derive(AddIn)]
+#[add_in_prop(
+    ty = Int,
+    name = "Limit",
+    name_ru = "Лимит",
+    get = Self::limit,
+    set = Self::set_limit,
+    set_result
+)]
+struct SetResultAddIn {
+    #[add_in_con]
+    connection: Option<&'static Connection>,
+
+    limit: i32,
+}
+
+impl SetResultAddIn {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn set_limit(&mut self, limit: i32) -> Result<(), &'static str> {
+        if limit == 0 {
+            return Err("limit can't be 0");
+        }
+        self.limit = limit;
+        Ok(())
+    }
+}
+
+#[rstest]
+fn test_set_result_only(platform: &'static FakePlatform) {
+    let mut add_in = SetResultAddIn {
+        connection: None,
+        limit: 10,
+    };
+    assert!(add_in.init(platform.connection()));
+
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::I32(10)));
+    assert_eq!(add_in.set_prop_val(0, ParamValue::I32(5)), Ok(()));
+    assert_eq!(add_in.set_prop_val(0, ParamValue::I32(0)), Err(()));
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::I32(5)));
+    assert_eq!(
+        platform.state().errors[0].description,
+        "Limit: limit can't be 0"
+    );
+}
+
+#[derive(AddIn)]
+struct OptionalPropAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(
+        ty = Str,
+        name = OPTIONAL_PROP_NAME,
+        name_ru = OPTIONAL_PROP_NAME_RU,
+        readable,
+        writable
+    )]
+    optional_property: Option<String>,
+}
+
+#[fixture]
+fn optional_add_in() -> OptionalPropAddIn {
+    OptionalPropAddIn {
+        connection: Arc::new(None),
+        optional_property: None,
+    }
+}
+
+#[rstest]
+fn test_get_optional_prop_val(mut optional_add_in: OptionalPropAddIn) {
+    use native_api_1c::native_api_1c_core::ffi::string_utils::os_string;
+
+    assert_eq!(optional_add_in.get_prop_val(0), Ok(ParamValue::Empty));
+
+    optional_add_in.optional_property = Some("value".to_string());
+    assert_eq!(
+        optional_add_in.get_prop_val(0),
+        Ok(ParamValue::String(os_string("value")))
+    );
+}
+
+#[rstest]
+fn test_set_optional_prop_val(mut optional_add_in: OptionalPropAddIn) {
+    use native_api_1c::native_api_1c_core::ffi::string_utils::os_string;
+
+    let result = optional_add_in.set_prop_val(0, ParamValue::String(os_string("value")));
+    assert_eq!(result, Ok(()));
+    assert_eq!(optional_add_in.optional_property.as_deref(), Some("value"));
+
+    let result = optional_add_in.set_prop_val(0, ParamValue::Empty);
+    assert_eq!(result, Ok(()));
+    assert_eq!(optional_add_in.optional_property, None);
+
+    let result = optional_add_in.set_prop_val(0, ParamValue::I32(1));
+    assert_eq!(result, Err(()));
+}
@@ -0,0 +1,236 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{platform, FakePlatform};
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+#[derive(AddIn)]
+#[add_in(profile = "ScalesDriver")]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_setting(key = "Port", default = 8080)]
+    port: u32,
+
+    #[add_in_setting(key = "Host", default = "localhost")]
+    host: String,
+
+    #[add_in_setting(key = "Verbose")]
+    verbose: bool,
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        port: 0,
+        host: String::new(),
+        verbose: true,
+    }
+}
+
+#[rstest]
+fn test_defaults(mut add_in: TestAddIn, platform: &'static FakePlatform) {
+    assert!(add_in.init(platform.connection()));
+
+    assert_eq!(
+        platform.state().profile_name.as_deref(),
+        Some("ScalesDriver")
+    );
+    assert_eq!(add_in.port, 8080);
+    assert_eq!(add_in.host, "localhost");
+    // without default the initial value is kept
+    assert!(add_in.verbose);
+}
+
+#[rstest]
+fn test_load(mut add_in: TestAddIn, platform: &'static FakePlatform) {
+    {
+        let mut state = platform.state();
+        let profile = state.profile("ScalesDriver");
+        profile.insert("Port".to_string(), ParamValue::I32(9100));
+        profile.insert(
+            "Host".to_string(),
+            ParamValue::String(os_string("scales.local")),
+        );
+        profile.insert("Verbose".to_string(), ParamValue::Bool(false));
+    }
+    assert!(add_in.init(platform.connection()));
+
+    assert_eq!(add_in.port, 9100);
+    assert_eq!(add_in.host, "scales.local");
+    assert!(!add_in.verbose);
+    assert!(platform.state().errors.is_empty());
+}
+
+#[rstest]
+fn test_load_unexpected_type(mut add_in: TestAddIn, platform: &'static FakePlatform) {
+    platform
+        .state()
+        .profile("ScalesDriver")
+        .insert("Port".to_string(), ParamValue::String(os_string("9100")));
+    assert!(add_in.init(platform.connection()));
+
+    assert_eq!(add_in.port, 8080);
+    let errors = &platform.state().errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].source, "TestAddIn");
+    assert_eq!(
+        errors[0].description,
+        "setting `Port` is ignored: expected integer Number, got String"
+    );
+}
+
+#[rstest]
+fn test_profile_unavailable(mut add_in: TestAddIn, platform: &'static FakePlatform) {
+    {
+        let mut state = platform.state();
+        state.profile_unavailable = true;
+        state
+            .profile("ScalesDriver")
+            .insert("Port".to_string(), ParamValue::I32(9100));
+    }
+    assert!(add_in.init(platform.connection()));
+
+    assert_eq!(add_in.port, 8080);
+    assert_eq!(add_in.host, "localhost");
+    let state = platform.state();
+    let errors = &state.errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].description,
+        "profile `ScalesDriver` is not available, settings are not loaded"
+    );
+    drop(state);
+
+    // settings are not written into other profile
+    add_in.done();
+    assert_eq!(
+        platform.state().profile("ScalesDriver").get("Port"),
+        Some(&ParamValue::I32(9100))
+    );
+}
+
+#[rstest]
+fn test_save_on_done(mut add_in: TestAddIn, platform: &'static FakePlatform) {
+    assert!(add_in.init(platform.connection()));
+    add_in.port = 9200;
+    add_in.verbose = false;
+    add_in.done();
+
+    let mut state = platform.state();
+    let profile = state.profile("ScalesDriver");
+    assert_eq!(profile.get("Port"), Some(&ParamValue::I32(9200)));
+    assert_eq!(
+        profile.get("Host"),
+        Some(&ParamValue::String(os_string("localhost")))
+    );
+    assert_eq!(profile.get("Verbose"), Some(&ParamValue::Bool(false)));
+}
+
+#[rstest]
+fn test_done_without_init(mut add_in: TestAddIn) {
+    // without connection there is nowhere to save settings
+    add_in.done();
+    assert_eq!(add_in.port, 0);
+}
+
+#[derive(AddIn)]
+#[add_in(on_init = Self::start)]
+struct HookAddIn {
+    #[add_in_con]
+    connection: Option<&'static Connection>,
+
+    #[add_in_setting(key = "Port", default = 8080)]
+    port: u32,
+
+    started_port: u32,
+}
+
+impl HookAddIn {
+    fn start(&mut self) -> Result<(), String> {
+        self.started_port = self.port;
+        Ok(())
+    }
+}
+
+#[rstest]
+fn test_loaded_before_on_init(platform: &'static FakePlatform) {
+    let mut add_in = HookAddIn {
+        connection: None,
+        port: 0,
+        started_port: 0,
+    };
+    assert!(add_in.init(platform.connection()));
+
+    assert_eq!(platform.state().profile_name.as_deref(), Some("HookAddIn"));
+    assert_eq!(add_in.started_port, 8080);
+}
+
+#[derive(AddIn)]
+#[add_in(profile = "Printer")]
+struct PrinterAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_setting(key = "Port", default = 9100)]
+    port: u32,
+}
+
+#[derive(AddIn)]
+#[add_in(profile = "Shop")]
+struct ShopAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_setting(key = "Port", default = 8080)]
+    port: u32,
+
+    #[add_in_flatten(prefix = "Printer", prefix_ru = "Принтер")]
+    printer: PrinterAddIn,
+}
+
+#[rstest]
+fn test_nested_profiles(platform: &'static FakePlatform) {
+    let mut add_in = ShopAddIn {
+        connection: Arc::new(None),
+        port: 0,
+        printer: PrinterAddIn {
+            connection: Arc::new(None),
+            port: 0,
+        },
+    };
+    {
+        let mut state = platform.state();
+        state
+            .profile("Shop")
+            .insert("Port".to_string(), ParamValue::I32(8081));
+        state
+            .profile("Printer")
+            .insert("Port".to_string(), ParamValue::I32(9101));
+    }
+    assert!(add_in.init(platform.connection()));
+    assert_eq!(add_in.port, 8081);
+    assert_eq!(add_in.printer.port, 9101);
+
+    add_in.port = 8082;
+    add_in.printer.port = 9102;
+    add_in.done();
+
+    let mut state = platform.state();
+    assert_eq!(
+        state.profile("Shop").get("Port"),
+        Some(&ParamValue::I32(8082))
+    );
+    assert_eq!(
+        state.profile("Printer").get("Port"),
+        Some(&ParamValue::I32(9102))
+    );
+}
@@ -3,6 +3,10 @@ fn trybuild_props() {
     let t = trybuild::TestCases::new();
 
     t.pass("tests/trybuild/to_build/props.rs");
+    t.compile_fail("tests/trybuild/to_build/props/setter_result_dropped.rs");
+    t.compile_fail("tests/trybuild/to_build/props/error_not_display.rs");
+    t.compile_fail("tests/trybuild/to_build/props/get_result_without_get.rs");
+    t.compile_fail("tests/trybuild/to_build/props/unsupported_coercion.rs");
 }
 
 #[test]
@@ -15,6 +19,7 @@ fn trybuild_functions() {
     t.pass("tests/trybuild/to_build/functions/str_type.rs");
     t.pass("tests/trybuild/to_build/functions/date_type.rs");
     t.pass("tests/trybuild/to_build/functions/blob_type.rs");
+    t.pass("tests/trybuild/to_build/functions/any_type.rs");
 
     t.pass("tests/trybuild/to_build/functions/result/bool_type.rs");
     t.pass("tests/trybuild/to_build/functions/result/int_type.rs");
@@ -33,6 +38,87 @@ fn trybuild_functions() {
     t.pass("tests/trybuild/to_build/functions/defaults/int_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/float_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/str_type.rs");
-    t.compile_fail("tests/trybuild/to_build/functions/defaults/date_type.rs");
-    t.compile_fail("tests/trybuild/to_build/functions/defaults/blob_type.rs");
+    t.pass("tests/trybuild/to_build/functions/defaults/date_type.rs");
+    t.pass("tests/trybuild/to_build/functions/defaults/blob_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/defaults/invalid_date.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/defaults/invalid_blob.rs");
+
+    t.pass("tests/trybuild/to_build/functions/optional.rs");
+
+    t.compile_fail("tests/trybuild/to_build/functions/coercion/unsupported_flag.rs");
+
+    t.pass("tests/trybuild/to_build/functions/inferred/types.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/type_mismatch.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/result_mismatch.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/unknown_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/extra_arg.rs");
+}
+
+#[test]
+fn trybuild_methods() {
+    let t = trybuild::TestCases::new();
+
+    t.compile_fail("tests/trybuild/to_build/methods/field_functions.rs");
+    t.compile_fail("tests/trybuild/to_build/methods/self_by_value.rs");
+    t.compile_fail("tests/trybuild/to_build/methods/without_impl_methods.rs");
+}
+
+#[test]
+fn trybuild_names() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/names/valid_names.rs");
+    t.compile_fail("tests/trybuild/to_build/names/invalid_names.rs");
+    t.compile_fail("tests/trybuild/to_build/names/duplicate_names.rs");
+    t.compile_fail("tests/trybuild/to_build/names/const_names.rs");
+    t.compile_fail("tests/trybuild/to_build/names/methods_names.rs");
+}
+
+#[test]
+fn trybuild_generics() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/generics.rs");
+}
+
+#[test]
+fn trybuild_flatten() {
+    let t = trybuild::TestCases::new();
+
+    t.compile_fail("tests/trybuild/to_build/flatten/not_addin.rs");
+    t.compile_fail("tests/trybuild/to_build/flatten/invalid_prefix.rs");
+    t.compile_fail("tests/trybuild/to_build/flatten/missing_prefix_ru.rs");
+    t.compile_fail("tests/trybuild/to_build/flatten/duplicate_names.rs");
+}
+
+#[test]
+fn trybuild_settings() {
+    let t = trybuild::TestCases::new();
+
+    t.compile_fail("tests/trybuild/to_build/settings/without_connection.rs");
+    t.compile_fail("tests/trybuild/to_build/settings/duplicate_key.rs");
+}
+
+#[test]
+fn trybuild_connection() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/connection/handle_types.rs");
+    t.compile_fail("tests/trybuild/to_build/connection/unsupported_type.rs");
+    t.compile_fail("tests/trybuild/to_build/connection/duplicate.rs");
+}
+
+#[test]
+fn trybuild_extern_functions() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/extern_functions/class_names.rs");
+    t.pass("tests/trybuild/to_build/extern_functions/options.rs");
+    t.pass("tests/trybuild/to_build/extern_functions/dynamic.rs");
+    t.pass("tests/trybuild/to_build/extern_functions/factories.rs");
+    t.compile_fail("tests/trybuild/to_build/extern_functions/duplicate_names.rs");
+    t.compile_fail("tests/trybuild/to_build/extern_functions/duplicate_names_ru.rs");
+    t.compile_fail("tests/trybuild/to_build/extern_functions/invalid_name.rs");
+    t.compile_fail("tests/trybuild/to_build/extern_functions/uninferred_name.rs");
+    t.compile_fail("tests/trybuild/to_build/extern_functions/cfg_duplicate.rs");
 }
@@ -0,0 +1,14 @@
+use native_api_1c::native_api_1c_core::{
+    ffi::connection::Connection, interface::ConnectionHandle,
+};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Option<&'static Connection>,
+    #[add_in_con]
+    handle: ConnectionHandle,
+}
+
+fn main() {}
@@ -0,0 +1,65 @@
+use std::sync::{Arc, Mutex};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::connection::Connection, interface::ConnectionHandle,
+};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct NoConnection {
+    #[add_in_prop(ty = Str, name = "Name", name_ru = "Имя", readable)]
+    pub name: String,
+}
+
+#[derive(AddIn)]
+pub struct PlainConnection {
+    #[add_in_con]
+    platform: Option<&'static Connection>,
+}
+
+#[derive(AddIn)]
+pub struct ArcConnection {
+    #[add_in_con]
+    con: Arc<Option<&'static Connection>>,
+}
+
+#[derive(AddIn)]
+pub struct MutexConnection {
+    #[add_in_con]
+    con: Mutex<Option<&'static Connection>>,
+}
+
+#[derive(AddIn)]
+pub struct SharedConnection {
+    #[add_in_con]
+    con: Arc<Mutex<Option<&'static Connection>>>,
+}
+
+#[derive(AddIn)]
+pub struct HandleConnection {
+    #[add_in_con]
+    handle: ConnectionHandle,
+}
+
+#[derive(AddIn)]
+pub struct TupleConnection(#[add_in_con] ConnectionHandle);
+
+fn main() {
+    let _ = NoConnection {
+        name: String::new(),
+    };
+    let _ = PlainConnection { platform: None };
+    let _ = ArcConnection {
+        con: Arc::new(None),
+    };
+    let _ = MutexConnection {
+        con: Mutex::new(None),
+    };
+    let _ = SharedConnection {
+        con: Arc::new(Mutex::new(None)),
+    };
+    let handle = ConnectionHandle::new();
+    assert!(handle.get().is_none());
+    let _ = HandleConnection { handle };
+    let _ = TupleConnection(ConnectionHandle::new());
+}
@@ -0,0 +1,10 @@
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: &'static Connection,
+}
+
+fn main() {}
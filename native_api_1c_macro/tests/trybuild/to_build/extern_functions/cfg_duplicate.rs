@@ -0,0 +1,16 @@
+use native_api_1c_macro::{extern_functions, AddIn};
+
+#[derive(AddIn, Default)]
+pub struct Printer {}
+
+extern_functions! {
+    #[cfg(unix)]
+    Printer::default(),
+    #[cfg(windows)]
+    Printer::default(),
+    #[cfg(unix)]
+    #[add_in_component(name = "PRINTER")]
+    Printer::default(),
+}
+
+fn main() {}
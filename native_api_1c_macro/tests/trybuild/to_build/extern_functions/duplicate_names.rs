@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::{extern_functions, AddIn};
+
+#[derive(AddIn)]
+pub struct Printer {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+impl Printer {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+        }
+    }
+}
+
+extern_functions! {
+    Printer::new(),
+    #[add_in_component(name = "printer")]
+    Printer::new(),
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use native_api_1c::native_api_1c_core::interface::{DynamicAddIn, ParamValue};
+use native_api_1c_macro::{extern_functions, AddIn};
+
+#[derive(AddIn, Default)]
+pub struct Printer {}
+
+extern_functions! {
+    Printer::default(),
+    #[add_in_component(name = "Queries")]
+    DynamicAddIn::new("Queries").method("Run", "Выполнить", vec![None], |params| {
+        Ok(params[0].clone())
+    }),
+    #[add_in_component(name = "Empty")]
+    DynamicAddIn::new("Empty").property("Version", "Версия", || Ok(ParamValue::I32(1))),
+}
+
+fn main() {}
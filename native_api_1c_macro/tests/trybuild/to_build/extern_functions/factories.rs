@@ -0,0 +1,69 @@
+use std::sync::Mutex;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::string_utils::{get_str, os_string},
+    interface::{AddInWrapper, DynamicAddIn},
+};
+use native_api_1c_macro::{extern_functions, AddIn};
+
+static CREATED: Mutex<Vec<(&str, usize)>> = Mutex::new(Vec::new());
+
+#[derive(AddIn)]
+pub struct Printer {}
+
+impl Printer {
+    pub fn new(index: usize) -> Self {
+        CREATED.lock().unwrap().push(("Printer", index));
+        Self {}
+    }
+}
+
+#[derive(AddIn, Default)]
+pub struct Scanner {}
+
+fn make_device(index: usize) -> Box<dyn AddInWrapper> {
+    CREATED.lock().unwrap().push(("Device", index));
+    match std::env::var("DEVICE").as_deref() {
+        Ok("dynamic") => Box::new(DynamicAddIn::new("Device")),
+        _ => Box::new(Scanner::default()),
+    }
+}
+
+extern_functions! {
+    |index| Printer::new(index),
+    #[add_in_component(name = "Device", factory)]
+    make_device,
+    #[cfg(all())]
+    #[add_in_component(name = "Scanner")]
+    Scanner::default(),
+    #[cfg(any())]
+    #[add_in_component(name = "Scanner")]
+    Box::new(Scanner::default()) as Box<dyn AddInWrapper>,
+    #[cfg(any())]
+    Missing::new(),
+}
+
+fn create(name: &str) {
+    let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+    let mut component = std::ptr::null_mut();
+    unsafe {
+        assert_eq!(GetClassObject(name.as_ptr(), &mut component), 1);
+        assert_eq!(DestroyObject(&mut component), 0);
+    }
+}
+
+fn main() {
+    let names = unsafe { get_str(GetClassNames()) };
+    let mut expected = os_string("Printer|Device|Scanner");
+    expected.push(0);
+    assert_eq!(names, expected.as_slice());
+
+    create("Printer");
+    create("Device");
+    create("Printer");
+    create("Device");
+    assert_eq!(
+        *CREATED.lock().unwrap(),
+        vec![("Printer", 0), ("Device", 0), ("Printer", 1), ("Device", 1)]
+    );
+}
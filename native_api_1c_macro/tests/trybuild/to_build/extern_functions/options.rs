@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::{
+    connection::Connection, platform_capabilities, AppCapabilities, AttachType,
+};
+use native_api_1c_macro::{extern_functions, AddIn};
+
+#[derive(AddIn)]
+pub struct Printer {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+impl Printer {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+        }
+    }
+}
+
+extern_functions! {
+    #![extern_functions(attach_type = Isolated)]
+
+    Printer::new(),
+}
+
+fn main() {
+    assert!(matches!(GetAttachType(), AttachType::Isolated));
+
+    assert_eq!(platform_capabilities(), AppCapabilities::Invalid);
+    assert_eq!(SetPlatformCapabilities(2), AppCapabilities::LAST as i32);
+    assert_eq!(platform_capabilities(), AppCapabilities::Capabilities2);
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::{extern_functions, AddIn};
+
+#[derive(AddIn)]
+pub struct Printer {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+mod printer {
+    pub fn new_printer() -> super::Printer {
+        super::Printer {
+            connection: std::sync::Arc::new(None),
+        }
+    }
+}
+
+extern_functions! {
+    printer::new_printer(),
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct Logger {
+    #[add_in_prop(ty = Int, name = "Level", name_ru = "Уровень", readable)]
+    pub level: i32,
+}
+
+#[derive(AddIn)]
+pub struct Jobs {
+    #[add_in_func(name = "Start", name_ru = "Запустить")]
+    pub start: fn(),
+}
+
+#[derive(AddIn)]
+pub struct Tasks {
+    #[add_in_func(name = "START", name_ru = "Начать")]
+    pub start: fn(),
+}
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Int, name = "LogLevel", name_ru = "Уровень", readable)]
+    pub level: i32,
+
+    #[add_in_flatten(prefix = "Log", prefix_ru = "Журнал")]
+    pub logger: Logger,
+
+    #[add_in_flatten]
+    pub jobs: Jobs,
+
+    #[add_in_flatten]
+    pub tasks: Tasks,
+}
+
+fn main() {}
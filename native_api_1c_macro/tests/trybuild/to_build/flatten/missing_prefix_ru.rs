@@ -0,0 +1,15 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct Settings {
+    #[add_in_prop(ty = Int, name = "Port", name_ru = "Порт", readable)]
+    port: i32,
+}
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_flatten(prefix = "Settings")]
+    settings: Settings,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use native_api_1c_macro::AddIn;
+
+struct Settings {
+    port: i32,
+}
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_flatten]
+    settings: Settings,
+}
+
+fn main() {}
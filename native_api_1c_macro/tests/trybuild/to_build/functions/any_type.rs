@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{ffi::connection::Connection, interface::ParamValue};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Any, name = "MyProp", name_ru = "МоеСвойство", readable, writable)]
+    pub my_prop: ParamValue,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = Any)]
+    #[returns(ty = Any, result)]
+    pub my_function: fn(&Self, ParamValue) -> Result<ParamValue, ()>,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    #[arg(ty = Any, as_out)]
+    #[arg(ty = Any)]
+    pub my_procedure: fn(&mut Self, &mut ParamValue, Option<ParamValue>),
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_prop: ParamValue::Empty,
+            my_function: Self::my_function_inner,
+            my_procedure: Self::my_procedure_inner,
+        }
+    }
+
+    fn my_function_inner(&self, arg: ParamValue) -> Result<ParamValue, ()> {
+        match arg {
+            ParamValue::I32(val) => Ok(ParamValue::F64(val as f64)),
+            ParamValue::F64(val) => Ok(ParamValue::I32(val as i32)),
+            _ => Err(()),
+        }
+    }
+
+    fn my_procedure_inner(&mut self, out_arg: &mut ParamValue, fallback: Option<ParamValue>) {
+        *out_arg = std::mem::replace(&mut self.my_prop, fallback.unwrap_or(ParamValue::Empty));
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
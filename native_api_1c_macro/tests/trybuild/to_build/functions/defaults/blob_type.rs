@@ -12,6 +12,11 @@ pub struct MyAddIn {
     #[arg(ty = Blob, default = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9])]
     #[returns(ty = Blob)]
     pub my_function: fn(&Self, Vec<u8>) -> Vec<u8>,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    #[arg(ty = Blob, default = b"\x01\x02")]
+    #[arg(ty = Blob, default = b"")]
+    pub my_procedure: fn(Vec<u8>, Vec<u8>),
 }
 
 impl MyAddIn {
@@ -19,6 +24,7 @@ impl MyAddIn {
         Self {
             connection: Arc::new(None),
             my_function: Self::my_function_inner,
+            my_procedure: |_, _| {},
         }
     }
 
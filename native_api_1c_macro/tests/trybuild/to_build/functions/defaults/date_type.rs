@@ -9,9 +9,14 @@ pub struct MyAddIn {
     connection: Arc<Option<&'static Connection>>,
 
     #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
-    #[arg(ty = Date, default = "2021-01-01T00:00:00+00:00")]
+    #[arg(ty = Date, default = "2021-01-01T00:00:00")]
     #[returns(ty = Date)]
     pub my_function: fn(&Self, chrono::NaiveDateTime) -> chrono::NaiveDateTime,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    #[arg(ty = Date, default = "2024-02-29")]
+    #[arg(ty = Date, default = "")]
+    pub my_procedure: fn(chrono::NaiveDateTime, chrono::NaiveDateTime),
 }
 
 impl MyAddIn {
@@ -19,6 +24,7 @@ impl MyAddIn {
         Self {
             connection: Arc::new(None),
             my_function: Self::my_function_inner,
+            my_procedure: |_, _| {},
         }
     }
 
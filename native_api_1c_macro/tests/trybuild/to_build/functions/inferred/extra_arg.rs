@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = Int)]
+    #[arg(ty = Int)]
+    pub my_function: fn(&Self, i32),
+}
+
+fn main() {}
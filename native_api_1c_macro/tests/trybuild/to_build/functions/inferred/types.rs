@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{ffi::connection::Connection, interface::ParamValue};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    pub my_function: fn(&Self, i32, i64, f64, bool, String) -> Result<String, ()>,
+
+    #[add_in_func(name = "MyFunctionDefault", name_ru = "МояФункцияПоУмолчанию")]
+    #[arg(default = 1)]
+    pub my_function_default: fn(u32, Option<chrono::NaiveDateTime>) -> Option<Vec<u8>>,
+
+    #[add_in_func(name = "MyFunctionOut", name_ru = "МояФункцияВывод")]
+    #[arg(ty = Str)]
+    pub my_function_out: fn(&mut Self, &mut String, ParamValue) -> ParamValue,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    pub my_procedure: fn(&mut Self) -> Result<(), ()>,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: Self::my_function_inner,
+            my_function_default: |count, _date| Some(vec![0; count as usize]),
+            my_function_out: |_, out_arg, value| {
+                out_arg.push('!');
+                value
+            },
+            my_procedure: |_| Ok(()),
+        }
+    }
+
+    fn my_function_inner(
+        &self,
+        int: i32,
+        long: i64,
+        float: f64,
+        flag: bool,
+        string: String,
+    ) -> Result<String, ()> {
+        Ok(format!("{int} {long} {float} {flag} {string}"))
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+type MaybeString = Option<String>;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = Str, optional)]
+    #[arg(ty = Int, default = 1)]
+    #[returns(ty = Str, optional)]
+    pub my_function: fn(&Self, MaybeString, i32) -> MaybeString,
+
+    #[add_in_func(name = "MyFunctionOut", name_ru = "МояФункцияВывод")]
+    #[arg(ty = Date, as_out)]
+    #[returns(ty = Blob, result)]
+    pub my_function_out: fn(&mut Option<chrono::NaiveDateTime>) -> Result<Option<Vec<u8>>, ()>,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    #[arg(ty = Bool)]
+    #[arg(ty = Float)]
+    pub my_procedure: fn(&mut Self, Option<bool>, Option<f64>),
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: Self::my_function_inner,
+            my_function_out: Self::my_function_out_inner,
+            my_procedure: Self::my_procedure_inner,
+        }
+    }
+
+    fn my_function_inner(&self, arg: MaybeString, count: i32) -> MaybeString {
+        arg.map(|arg| arg.repeat(count as usize))
+    }
+
+    fn my_function_out_inner(out_arg: &mut Option<chrono::NaiveDateTime>) -> Result<Option<Vec<u8>>, ()> {
+        *out_arg = None;
+        Ok(None)
+    }
+
+    fn my_procedure_inner(&mut self, _flag: Option<bool>, _value: Option<f64>) {}
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
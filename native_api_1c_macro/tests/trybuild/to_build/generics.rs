@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::{add_in_methods, extern_functions, AddIn};
+
+pub trait Storage: Default + 'static {
+    const KIND: &'static str;
+}
+
+#[derive(Default)]
+pub struct Database;
+
+impl Storage for Database {
+    const KIND: &'static str = "Database";
+}
+
+#[derive(Default)]
+pub struct Memory;
+
+impl Storage for Memory {
+    const KIND: &'static str = "Memory";
+}
+
+const KIND_NAME: &str = "Kind";
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+pub struct Repository<S: Storage> {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Str, name = KIND_NAME, name_ru = "Вид", get = Self::kind)]
+    storage: S,
+}
+
+#[add_in_methods]
+impl<S: Storage> Repository<S> {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            storage: S::default(),
+        }
+    }
+
+    fn kind(&self) -> String {
+        S::KIND.to_string()
+    }
+
+    #[add_in_func(name = "Clear", name_ru = "Очистить")]
+    fn clear(&mut self) {
+        self.storage = S::default();
+    }
+}
+
+extern_functions! {
+    #[add_in_component(name = "Repository")]
+    Repository::<Database>::new(),
+    #[add_in_component(name = "TestRepository")]
+    Repository::<Memory>::new(),
+}
+
+fn main() {}
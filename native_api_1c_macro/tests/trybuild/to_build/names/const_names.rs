@@ -0,0 +1,29 @@
+use native_api_1c_macro::AddIn;
+
+const COUNT_NAME: &str = "Count";
+const INVALID_NAME: &str = "Run now";
+const NUMBER_NAME_RU: &str = "№";
+const STOP_NAME_RU: &str = "ОСТАНОВИТЬ";
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Int, name = COUNT_NAME, name_ru = "Количество", readable)]
+    pub count: i32,
+
+    #[add_in_func(name = "COUNT", name_ru = "Посчитать")]
+    pub recount: fn(),
+
+    #[add_in_func(name = INVALID_NAME, name_ru = "Запустить")]
+    pub run: fn(),
+
+    #[add_in_func(name = "Number", name_ru = NUMBER_NAME_RU)]
+    pub number: fn(),
+
+    #[add_in_func(name = "Stop", name_ru = "Остановить")]
+    pub stop: fn(),
+
+    #[add_in_func(name = "Halt", name_ru = STOP_NAME_RU)]
+    pub halt: fn(),
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Int, name = "Count", name_ru = "Количество", readable)]
+    pub count: i32,
+
+    #[add_in_func(name = "count", name_ru = "Посчитать")]
+    pub recount: fn(),
+
+    #[add_in_func(name = "Run", name_ru = "КОЛИЧЕСТВО")]
+    pub run: fn(),
+
+    #[add_in_func(name = "Stop", name_ru = "Остановить")]
+    pub stop: fn(),
+
+    #[add_in_func(name = "Halt", name_ru = "ОСТАНОВИТЬ")]
+    pub halt: fn(),
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Int, name = "1Count", name_ru = "Количество", readable)]
+    pub count: i32,
+
+    #[add_in_func(name = "Run Now", name_ru = "Запустить-сейчас")]
+    pub run: fn(),
+
+    #[add_in_func(name = "Number", name_ru = "№")]
+    pub number: fn(),
+
+    #[add_in_func(name = "Dash", name_ru = "Тире—2")]
+    pub dash: fn(),
+}
+
+fn main() {}
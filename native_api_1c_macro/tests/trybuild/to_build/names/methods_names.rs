@@ -0,0 +1,19 @@
+use native_api_1c_macro::{add_in_methods, AddIn};
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Int, name = "Reset", name_ru = "Количество", readable)]
+    pub count: i32,
+}
+
+#[add_in_methods]
+impl MyAddIn {
+    #[add_in_func(name = "Reset", name_ru = "Сбросить")]
+    pub fn reset(&mut self) {}
+
+    #[add_in_func(name = "Run", name_ru = "Запустить")]
+    pub fn run(&mut self) {}
+}
+
+fn main() {}
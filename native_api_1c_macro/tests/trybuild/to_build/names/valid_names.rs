@@ -0,0 +1,41 @@
+use native_api_1c_macro::{add_in_methods, AddIn};
+
+const STATUS_NAME: &str = "Status";
+const STATUS_NAME_RU: &str = "Статус";
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Str, name = STATUS_NAME, name_ru = STATUS_NAME_RU, readable)]
+    pub status: String,
+
+    #[add_in_prop(ty = Int, name = "_Count2", name_ru = "Количество_2", readable)]
+    pub count: i32,
+
+    // English and Russian names can be the same
+    #[add_in_func(name = "Run", name_ru = "run")]
+    pub run: fn(),
+}
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+pub struct MethodsAddIn {
+    #[add_in_prop(ty = Str, name = STATUS_NAME, name_ru = STATUS_NAME_RU, readable)]
+    pub status: String,
+}
+
+#[add_in_methods]
+impl MethodsAddIn {
+    #[add_in_func(name = "Reset", name_ru = "Сбросить")]
+    pub fn reset(&mut self) {}
+}
+
+fn main() {
+    let _ = MyAddIn {
+        status: String::new(),
+        count: 0,
+        run: || {},
+    };
+    let _ = MethodsAddIn {
+        status: String::new(),
+    };
+}
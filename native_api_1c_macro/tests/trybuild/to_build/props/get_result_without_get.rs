@@ -0,0 +1,9 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Int, name = "Port", name_ru = "Порт", readable, get_result)]
+    port: i32,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+#[add_in_prop(ty = Int, name = "Port", name_ru = "Порт", get = Self::port, set = Self::set_port)]
+pub struct MyAddIn {
+    port: i32,
+}
+
+impl MyAddIn {
+    fn port(&self) -> i32 {
+        self.port
+    }
+
+    fn set_port(&mut self, port: i32) -> Result<(), String> {
+        self.port = port;
+        Ok(())
+    }
+}
+
+fn main() {}
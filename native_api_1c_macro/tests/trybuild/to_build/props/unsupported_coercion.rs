@@ -0,0 +1,9 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_prop(ty = Int64, name = "Size", name_ru = "Размер", readable, writable, coerce_string)]
+    size: i64,
+}
+
+fn main() {}
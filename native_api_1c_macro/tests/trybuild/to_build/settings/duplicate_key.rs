@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_setting(key = "Port", default = 8080)]
+    port: u32,
+
+    #[add_in_setting(key = "Port")]
+    backup_port: u32,
+}
+
+fn main() {}
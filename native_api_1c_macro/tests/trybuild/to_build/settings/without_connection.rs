@@ -0,0 +1,9 @@
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_setting(key = "Port", default = 8080)]
+    port: u32,
+}
+
+fn main() {}